@@ -2,13 +2,16 @@
 #![doc = include_str!("examples.md")]
 #![no_std]
 
-#[cfg(any(test, feature = "arbitrary", feature = "speedy"))]
+// These dependencies do not support `no_std` themselves, and their derives
+// expand to `std` paths, so `alloc` alone is not enough for them.
+#[cfg(any(test, feature = "arbitrary", feature = "databuf", feature = "speedy"))]
 extern crate std;
 
-#[cfg(feature = "bitcode")]
+// Enabled by features that only need `alloc`, like `bitcode`.
+#[cfg(feature = "alloc")]
 extern crate alloc;
 
-#[cfg(feature = "bitcode")]
+#[cfg(feature = "alloc")]
 use alloc::vec::Vec;
 
 #[cfg(test)]
@@ -20,7 +23,13 @@ mod tests_prop;
 #[cfg(test)]
 mod tests_readme;
 
+#[cfg(kani)]
+mod proofs;
+
 use core::cmp::Ordering;
+use core::fmt;
+use core::iter::FusedIterator;
+use core::num::NonZeroU16;
 use core::ops::Add;
 
 /// Two-byte serial number with wraparound.
@@ -49,11 +58,64 @@ use core::ops::Add;
 /// The final value in the number space, `u16::MAX`, is reserved for the special
 /// [`NAN`](Self::NAN) value. This is done to save space - you don't need to wrap
 /// this type in an `Option` if only some items are assigned a serial number.
+///
+/// Since this ordering is not transitive, `Serial` does not implement `PartialOrd`
+/// or `Ord` by default, and there are no comparison operators. Use these methods instead:
+///
+/// | Operator | Method                                 |
+/// |----------|----------------------------------------|
+/// | `a < b`  | [`a.precedes(b)`](Self::precedes)      |
+/// | `a <= b` | [`a.precedes_or_eq(b)`](Self::precedes_or_eq) |
+/// | `a > b`  | [`a.succeeds(b)`](Self::succeeds)      |
+/// | `a >= b` | [`a.succeeds_or_eq(b)`](Self::succeeds_or_eq) |
+/// | `a.partial_cmp(&b)` | [`a.partial_cmp(b)`](Self::partial_cmp) |
+/// | `a.min(b)`, `a.max(b)` | [`a.min(b)`](Self::min), [`a.max(b)`](Self::max) |
+///
+/// If all numbers you compare fit into one window, the `unsafe-total-order` feature
+/// implements `Ord` and `PartialOrd` for use with sorting and ordered collections.
+///
+/// To migrate code that was written for `PartialOrd`, `cmp()` returns the same as
+/// [`partial_cmp()`](Self::partial_cmp) without the `unsafe-total-order` feature.
+/// Rust cannot deprecate operators, so `a < b` does not compile without the feature:
+/// use the methods above instead.
+///
+/// This type is `#[repr(transparent)]`, so it has the same layout and ABI as a `u16`,
+/// and can be passed across an FFI boundary as a C `uint16_t`. See also [`SerialC`].
+///
+/// This is [`SentinelSerial`] with `u16::MAX` as the sentinel for [`NAN`](Self::NAN).
 #[doc = include_str!("examples.md")]
+pub type Serial = SentinelSerial<{ u16::MAX }>;
+
+/// Two-byte serial number with wraparound, that reserves the `SENTINEL` value
+/// for the special [`NAN`](Self::NAN) value.
+///
+/// Use the [`Serial`] alias, which reserves `u16::MAX`, or [`ZeroNanSerial`],
+/// which reserves `0`. Any other sentinel works the same way: the remaining 65535
+/// values form the ring of numbers, that starts right after the sentinel, and
+/// wraps around right before it.
+///
+/// Every sentinel has the same methods, and they behave exactly like they do on the
+/// [`Serial`] that you get from [`to_serial()`](Self::to_serial). Only the raw value
+/// is different, which is what [`new_unchecked()`](Self::new_unchecked) takes,
+/// what the `Debug` output shows, and what is stored by the derived traits of the
+/// feature flags, and by casts like `as_u16_slice()`.
+///
+/// ```
+/// use serial_num::{SentinelSerial, Serial};
+///
+/// type SevenNanSerial = SentinelSerial<7>;
+///
+/// assert!(SevenNanSerial::new_unchecked(7).is_nan());
+/// assert_eq!(SevenNanSerial::new_unchecked(8), SevenNanSerial::ZERO);
+///
+/// let num = SevenNanSerial::new_unchecked(6);
+/// assert!(num.precedes(num + 1));
+/// assert_eq!(SevenNanSerial::ZERO, num + 1); // wraparound
+/// assert_eq!(Serial::ZERO + 65534, num.to_serial());
+/// ```
 #[must_use]
 #[repr(transparent)]
-#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
-#[cfg_attr(feature = "arbitrary", derive(arbitrary::Arbitrary))]
+#[derive(Copy, Clone, PartialEq, Eq, Hash)]
 #[cfg_attr(feature = "bincode", derive(bincode::Decode, bincode::Encode))]
 #[cfg_attr(feature = "bitcode", derive(bitcode::Decode, bitcode::Encode))]
 #[cfg_attr(
@@ -61,9 +123,10 @@ use core::ops::Add;
     derive(borsh::BorshDeserialize, borsh::BorshSerialize)
 )]
 #[cfg_attr(feature = "bytemuck", derive(bytemuck::Pod, bytemuck::Zeroable))]
+#[cfg_attr(feature = "databuf", derive(databuf::Decode, databuf::Encode))]
 #[cfg_attr(
     feature = "postcard",
-    derive(postcard::experimental::max_size::MaxSize, postcard_schema::Schema)
+    derive(postcard::experimental::max_size::MaxSize)
 )]
 #[cfg_attr(
     feature = "rkyv",
@@ -73,9 +136,13 @@ use core::ops::Add;
     feature = "rkyv",
     rkyv(compare(PartialEq), derive(Clone, Copy, Debug),)
 )]
-#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
-#[cfg_attr(feature = "speedy", derive(speedy::Readable, speedy::Writable))]
-pub struct Serial(u16);
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SentinelSerial<const SENTINEL: u16>(u16);
+
+/// The archived form of a [`Serial`], which can be compared with a live [`Serial`].
+#[cfg(feature = "rkyv")]
+pub type ArchivedSerial = ArchivedSentinelSerial<{ u16::MAX }>;
 
 const NAN_U16: u16 = u16::MAX;
 const NAN_U32: u32 = 65_535;
@@ -83,17 +150,135 @@ const MAX_U16: u16 = u16::MAX - 1;
 const MID_I32: i32 = 32_767;
 const MID_U16: u16 = 32_767;
 
-impl Serial {
+/// Numbers at the edges of the number space, and of the window.
+#[cfg(any(test, feature = "arbitrary", feature = "self-check"))]
+const CANDIDATES: [u16; 10] = [
+    0,
+    1,
+    2,
+    MID_U16 - 1,
+    MID_U16,
+    MID_U16 + 1,
+    MAX_U16 - 2,
+    MAX_U16 - 1,
+    MAX_U16,
+    NAN_U16,
+];
+
+impl<const SENTINEL: u16> SentinelSerial<SENTINEL> {
     /// Special value representing "no serial number".
     ///
     /// By convention, this "number" cannot be increased, or added to.
-    pub const NAN: Self = Self(NAN_U16);
+    pub const NAN: Self = Self(SENTINEL);
+
+    /// The initial serial number, which is also the [`Serial::default()`].
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// const FIRST: Serial = Serial::ZERO;
+    /// assert_eq!(Serial::default(), FIRST);
+    /// ```
+    pub const ZERO: Self = Self::from_logical(0);
+
+    /// The number that is a quarter of the number space, `16383` increases,
+    /// ahead of [`ZERO`](Self::ZERO).
+    pub const QUARTER: Self = Self::from_logical(16_383);
+
+    /// The number that is half of the number space, [`WINDOW`](Self::WINDOW) increases,
+    /// ahead of [`ZERO`](Self::ZERO). This is the furthest number that still succeeds it.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert!(Serial::ZERO.precedes(Serial::HALF));
+    /// assert!(Serial::ZERO.succeeds(Serial::HALF + 1)); // flipped
+    /// assert!(Serial::ZERO.precedes(Serial::QUARTER));
+    /// assert!(Serial::QUARTER.precedes(Serial::HALF));
+    /// ```
+    pub const HALF: Self = Self::from_logical(MID_U16);
+
+    /// The largest [distance](Self::dist) at which numbers are compared as you
+    /// normally would, which is half of the number space, `(u16::MAX-1)/2 = 32767`.
+    ///
+    /// Numbers that are further apart are compared with the ordering flipped.
+    pub const WINDOW: u16 = MID_U16;
+
+    /// Creates a serial number from its raw counter value.
+    ///
+    /// No validation takes place: the `SENTINEL`, which is `u16::MAX` for [`Serial`],
+    /// becomes [`NAN`](Self::NAN), and other values are taken as they are.
+    ///
+    /// Since this is a `const fn`, it can be used to create tables of serial
+    /// numbers at compile time.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// const TABLE: [Serial; 3] = [
+    ///     Serial::new_unchecked(0),
+    ///     Serial::new_unchecked(1),
+    ///     Serial::new_unchecked(2),
+    /// ];
+    /// assert_eq!(TABLE[0] + 2, TABLE[2]);
+    /// assert!(Serial::new_unchecked(u16::MAX).is_nan());
+    /// ```
+    #[inline]
+    pub const fn new_unchecked(n: u16) -> Self {
+        Self(n)
+    }
+
+    /// Converts to the [`Serial`] at the same position on the ring.
+    ///
+    /// The [`ZERO`](Self::ZERO) of every sentinel becomes [`Serial::ZERO`],
+    /// and [`NAN`](Self::NAN) becomes [`Serial::NAN`].
+    ///
+    /// ```
+    /// use serial_num::{Serial, ZeroNanSerial};
+    ///
+    /// assert_eq!(Serial::ZERO, ZeroNanSerial::new_unchecked(1).to_serial());
+    /// assert_eq!(Serial::NAN, ZeroNanSerial::new_unchecked(0).to_serial());
+    /// assert_eq!(Serial::ZERO + 65534, ZeroNanSerial::new_unchecked(u16::MAX).to_serial());
+    /// ```
+    #[inline]
+    pub const fn to_serial(self) -> Serial {
+        Serial::new_unchecked(self.logical())
+    }
+
+    /// Converts from the [`Serial`] at the same position on the ring.
+    ///
+    /// This is the reverse of [`Self::to_serial()`].
+    #[inline]
+    pub const fn from_serial(serial: Serial) -> Self {
+        Self::from_logical(serial.0)
+    }
+
+    /// The same as `self == other`, but usable in `const` contexts.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// const ZERO_IS_NOT_NAN: () = assert!(!Serial::ZERO.const_eq(Serial::NAN));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub const fn const_eq(self, other: Self) -> bool {
+        self.0 == other.0
+    }
 
     /// Returns `true` if this number is [`NAN`](Self::NAN).
     #[inline]
     #[must_use]
-    pub fn is_nan(self) -> bool {
-        self == Self::NAN
+    pub const fn is_nan(self) -> bool {
+        self.const_eq(Self::NAN)
+    }
+
+    /// Returns `true` if this number is the initial [`Serial::default()`],
+    /// that was never increased, or added to.
+    #[inline]
+    #[must_use]
+    pub const fn is_initial(self) -> bool {
+        self.const_eq(Self::ZERO)
     }
 
     /// Increases `self` with wraparound.
@@ -103,10 +288,93 @@ impl Serial {
         if self.is_nan() {
             return;
         }
-        if self.0 < MAX_U16 {
-            self.0 += 1;
+        let n = self.logical();
+        *self = if n < MAX_U16 {
+            Self::from_logical(n + 1)
         } else {
-            self.0 = 0; // wraparound
+            Self::from_logical(0) // wraparound
+        };
+    }
+
+    /// Increases every number in `serials` with wraparound.
+    ///
+    /// This is the same as calling [`Self::increase()`] on each of them,
+    /// so [`NAN`](Self::NAN) numbers are left unchanged.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut serials = [Serial::default(), Serial::NAN, Serial::default() + 65534];
+    /// Serial::increase_all(&mut serials);
+    /// assert_eq!([Serial::default() + 1, Serial::NAN, Serial::default()], serials);
+    /// ```
+    #[inline]
+    pub fn increase_all(serials: &mut [Self]) {
+        serials.iter_mut().for_each(Self::increase);
+    }
+
+    /// Increases `self` with wraparound, and reports what happened.
+    ///
+    /// This behaves like [`Self::increase()`], but distinguishes a normal increase
+    /// from one that wrapped around to `0`, for state machines that react to it.
+    ///
+    /// ```
+    /// use serial_num::{IncreaseOutcome, Serial};
+    ///
+    /// let mut serial = Serial::default() + 65534;
+    /// assert_eq!(IncreaseOutcome::Wrapped, serial.increase_report());
+    /// assert_eq!(Serial::default(), serial);
+    /// assert_eq!(IncreaseOutcome::Advanced, serial.increase_report());
+    ///
+    /// let mut nan = Serial::NAN;
+    /// assert_eq!(IncreaseOutcome::WasNan, nan.increase_report());
+    /// assert!(nan.is_nan());
+    /// ```
+    #[inline]
+    pub fn increase_report(&mut self) -> IncreaseOutcome {
+        let outcome = match self.logical() {
+            NAN_U16 => IncreaseOutcome::WasNan,
+            MAX_U16 => IncreaseOutcome::Wrapped,
+            _ => IncreaseOutcome::Advanced,
+        };
+        self.increase();
+        outcome
+    }
+
+    /// Atomically takes the next serial number from a `counter` that you own,
+    /// and increases it with wraparound.
+    ///
+    /// This is the atomic version of [`Self::get_increase()`]: it returns the number
+    /// before the increase, and a counter that holds [`NAN`](Self::NAN) stays
+    /// [`NAN`](Self::NAN). Concurrent calls never return the same number, until the
+    /// counter wraps around.
+    ///
+    /// `order` is the ordering of the read-modify-write operation, like for
+    /// `AtomicU16::fetch_add()`.
+    ///
+    /// ```
+    /// use core::sync::atomic::{AtomicU16, Ordering};
+    /// use serial_num::Serial;
+    ///
+    /// let counter = AtomicU16::new(65534);
+    /// assert_eq!(Serial::default() + 65534, Serial::assign_next(&counter, Ordering::Relaxed));
+    /// assert_eq!(Serial::default(), Serial::assign_next(&counter, Ordering::Relaxed));
+    /// assert_eq!(1, counter.load(Ordering::Relaxed));
+    /// ```
+    #[cfg(target_has_atomic = "16")]
+    #[inline]
+    pub fn assign_next(
+        counter: &core::sync::atomic::AtomicU16,
+        order: core::sync::atomic::Ordering,
+    ) -> Self {
+        // The value is returned by the successful exchange, which uses `order`,
+        // so loads before retries can be relaxed.
+        let fetch_order = core::sync::atomic::Ordering::Relaxed;
+        let prev = counter.fetch_update(order, fetch_order, |inner| {
+            Some(Self::from_logical(inner).successor().logical())
+        });
+        match prev {
+            Ok(inner) | Err(inner) => Self::from_logical(inner),
         }
     }
 
@@ -117,6 +385,74 @@ impl Serial {
         *self
     }
 
+    /// Returns the number that follows `self`, with wraparound.
+    ///
+    /// This is the same as `self + 1`, so [`NAN`](Self::NAN) stays [`NAN`](Self::NAN).
+    #[inline]
+    pub fn successor(self) -> Self {
+        self.wrapping_add(1)
+    }
+
+    /// Returns an endless iterator over the numbers that follow `self`, with wraparound.
+    ///
+    /// The iterator starts at the [successor](Self::successor) of `self`.
+    /// If `self.is_nan()`, the iterator is empty.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let start = Serial::default() + 65533;
+    /// let next: Vec<_> = start.successors().take(3).collect();
+    /// assert_eq!(vec![start + 1, start + 2, start + 3], next);
+    /// assert_eq!(Serial::default(), start + 2); // wraparound
+    ///
+    /// assert_eq!(None, Serial::NAN.successors().next());
+    /// ```
+    #[inline]
+    pub const fn successors(self) -> Successors<SENTINEL> {
+        Successors { next: self }
+    }
+
+    /// `True` if `self` is exactly the [successor](Self::successor) of `prev`.
+    ///
+    /// This is stricter than [`Self::succeeds()`], since it does not allow gaps.
+    /// Returns `false` if either number is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let prev = Serial::default();
+    /// assert!((prev + 1).is_next_after(prev));
+    /// assert!(!(prev + 2).is_next_after(prev)); // gap
+    /// assert!(!prev.is_next_after(prev));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_next_after(self, prev: Self) -> bool {
+        !prev.is_nan() && self == prev.successor()
+    }
+
+    /// Returns the number a receiver expects next, after having seen `last_seen`.
+    ///
+    /// A receiver that has not seen any number yet holds [`NAN`](Self::NAN),
+    /// and expects the first number to be [`ZERO`](Self::ZERO).
+    /// Otherwise, this is the [successor](Self::successor) of `last_seen`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Serial::ZERO, Serial::expected_next(Serial::NAN));
+    /// assert_eq!(Serial::ZERO + 5, Serial::expected_next(Serial::ZERO + 4));
+    /// ```
+    #[inline]
+    pub fn expected_next(last_seen: Self) -> Self {
+        if last_seen.is_nan() {
+            Self::ZERO
+        } else {
+            last_seen.successor()
+        }
+    }
+
     /// Returns a copy of `self`, and increases `self` with wraparound.
     #[inline]
     pub fn get_increase(&mut self) -> Self {
@@ -125,6 +461,134 @@ impl Serial {
         num
     }
 
+    /// Increases `self` with wraparound, and returns a copy, unless its successor
+    /// is `live_low`.
+    ///
+    /// `live_low` is the oldest serial number that is still in use. If increasing `self`
+    /// would assign that same number again, the number space is exhausted, and `self`
+    /// is left unchanged.
+    ///
+    /// If `live_low` is [`NAN`](Self::NAN), there is no serial number in use, and this
+    /// is the same as [`Self::increase_get()`].
+    ///
+    /// # Errors
+    /// Returns [`WindowExhausted`] if the successor of `self` is `live_low`.
+    #[inline]
+    pub fn increase_checked_against(&mut self, live_low: Self) -> Result<Self, WindowExhausted> {
+        let mut next = *self;
+        next.increase();
+        if !self.is_nan() && next == live_low {
+            return Err(WindowExhausted);
+        }
+        *self = next;
+        Ok(next)
+    }
+
+    /// Returns the number of increases that are left until `self` wraps around.
+    ///
+    /// If this is `Some(0)`, the next increase will wraparound.
+    ///
+    /// Returns `None` if `self` is [`NAN`](Self::NAN).
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "cannot overflow for non-NAN values"
+    )]
+    pub fn steps_to_wrap(self) -> Option<u16> {
+        if self.is_nan() {
+            return None;
+        }
+        Some(MAX_U16 - self.logical())
+    }
+
+    /// Increases `self` with wraparound `times` times, and returns how often it wrapped around.
+    ///
+    /// A wraparound is the increase from `65534` to `0`. This is useful to keep track
+    /// of an absolute counter alongside the serial number.
+    ///
+    /// If `self` is [`NAN`](Self::NAN), it is left unchanged, and this returns `0`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut serial = Serial::default();
+    /// assert_eq!(0, serial.increase_counting(65534));
+    /// assert_eq!(1, serial.increase_counting(1));
+    /// assert!(serial.is_initial());
+    /// ```
+    #[inline]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        clippy::cast_possible_truncation,
+        clippy::as_conversions,
+        clippy::integer_division,
+        clippy::integer_division_remainder_used,
+        reason = "cannot overflow or truncate"
+    )]
+    pub fn increase_counting(&mut self, times: u32) -> u32 {
+        if self.is_nan() {
+            return 0;
+        }
+        let total = u64::from(self.logical()) + u64::from(times);
+        let len = u64::from(NAN_U32);
+        *self = Self::from_logical((total % len) as u16);
+        (total / len) as u32
+    }
+
+    /// Moves `self` back to `target`, if `target` precedes it.
+    ///
+    /// Returns `true` if `self` was changed. If `target` does not [precede](Self::precedes)
+    /// `self` within the comparison window, `self` is left unchanged, and this returns `false`.
+    /// This is also the case if either of them is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut serial = Serial::default() + 10;
+    /// assert!(serial.retreat_to(Serial::default() + 5));
+    /// assert_eq!(Serial::default() + 5, serial);
+    ///
+    /// assert!(!serial.retreat_to(Serial::default() + 8)); // not a rewind
+    /// assert_eq!(Serial::default() + 5, serial);
+    /// ```
+    #[inline]
+    pub fn retreat_to(&mut self, target: Self) -> bool {
+        if !target.precedes(*self) {
+            return false;
+        }
+        *self = target;
+        true
+    }
+
+    /// Moves `self` forward to `target`, if `target` succeeds it, for example to sync
+    /// a local counter with the current number of a peer.
+    ///
+    /// Returns the number of increases it took to get to `target`. If `target` does not
+    /// [succeed](Self::succeeds) `self` within the comparison window, `self` is left
+    /// unchanged, and this returns `0`. This is also the case if either of them
+    /// is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut serial = Serial::default() + 65530;
+    /// assert_eq!(10, serial.catch_up_to(Serial::default() + 5)); // across the wraparound
+    /// assert_eq!(Serial::default() + 5, serial);
+    ///
+    /// assert_eq!(0, serial.catch_up_to(Serial::default() + 2)); // never moves back
+    /// assert_eq!(Serial::default() + 5, serial);
+    /// ```
+    #[inline]
+    pub fn catch_up_to(&mut self, target: Self) -> u16 {
+        if !target.succeeds(*self) {
+            return 0;
+        }
+        let steps = target.dist(*self);
+        *self = target;
+        steps
+    }
+
     /// Distance with wraparound.
     ///
     /// For the signed difference, use [`Self::diff()`].
@@ -144,21 +608,140 @@ impl Serial {
         if self.is_nan() || other.is_nan() {
             return MID_U16; // max distance
         }
-        if self.0 == other.0 {
+        if self.logical() == other.logical() {
             return 0;
         }
 
         let min = self.min(other);
         let max = self.max(other);
 
-        if min.0 < max.0 {
+        let dist = if min.logical() < max.logical() {
             // min is predecessor, and counter is lower
             // distance is: min->max
-            max.0 - min.0
+            max.logical() - min.logical()
         } else {
             // min is predecessor, but counter is higher
             // distance is: min->MAX + 0->max + MAX->0
-            MAX_U16 - min.0 + max.0 + 1
+            MAX_U16 - min.logical() + max.logical() + 1
+        };
+        debug_assert!(dist <= MID_U16, "distance must fit into the window");
+        dist
+    }
+
+    /// Distance with wraparound, or `None` if one of the numbers is [`NAN`](Self::NAN).
+    ///
+    /// Unlike [`Self::dist()`], a distance of `32767` is always a real distance,
+    /// and never stands in for a [`NAN`](Self::NAN) number.
+    /// This is the length of the shorter arc between the two numbers, so it does
+    /// not exceed `32767` either. It is returned as a `u32`, which is what
+    /// arithmetic like [`Duration::checked_mul()`](core::time::Duration::checked_mul)
+    /// takes, without another conversion.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some(32767), a.dist_exact(a + 32767));
+    /// assert_eq!(Some(32767), a.dist_exact(a + 32768)); // shorter arc
+    /// assert_eq!(None, a.dist_exact(Serial::NAN));
+    /// assert_eq!(32767, a.dist(Serial::NAN)); // ambiguous
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn dist_exact(self, other: Self) -> Option<u32> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        Some(u32::from(self.dist(other)))
+    }
+
+    /// The number of bits that differ between the two counters, or `None`
+    /// if one of the numbers is [`NAN`](Self::NAN).
+    ///
+    /// This is the popcount of `a ^ b`, as used to partition a hash space,
+    /// for example in DHT-style bucketing. It is **not** the distance on the ring:
+    /// neighbouring numbers can differ in many bits, and distant numbers in few.
+    /// For the distance with wraparound, use [`Self::dist()`].
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 32767;
+    /// let b = a + 1;
+    /// assert_eq!(Some(16), a.xor_metric(b)); // 0x7FFF ^ 0x8000
+    /// assert_eq!(1, a.dist(b));
+    /// assert_eq!(None, a.xor_metric(Serial::NAN));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn xor_metric(self, other: Self) -> Option<u32> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        Some((self.logical() ^ other.logical()).count_ones())
+    }
+
+    /// The lengths of both arcs between two numbers on the ring, as `(forward, backward)`.
+    ///
+    /// `forward` is the number of increases it takes to get from `self` to `other`,
+    /// and `backward` is the number of increases from `other` to `self`.
+    /// Since there are `65535` numbers on the ring, the two add up to `65535` for
+    /// different numbers, and are both `0` for equal numbers.
+    /// The shorter of the two is the [`dist()`](Self::dist).
+    ///
+    /// Returns `None` if one of the numbers is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some((3, 65532)), a.arcs(a + 3));
+    /// assert_eq!(Some((65532, 3)), (a + 3).arcs(a));
+    /// assert_eq!(Some((0, 0)), a.arcs(a));
+    /// assert_eq!(None, a.arcs(Serial::NAN));
+    /// ```
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        clippy::cast_possible_truncation,
+        clippy::as_conversions,
+        reason = "cannot overflow or truncate for non-NAN values"
+    )]
+    pub fn arcs(self, other: Self) -> Option<(u16, u16)> {
+        if self.is_nan() || other.is_nan() {
+            return None;
+        }
+        if self.logical() == other.logical() {
+            return Some((0, 0));
+        }
+        let forward = (u32::from(other.logical()) + NAN_U32 - u32::from(self.logical())) % NAN_U32;
+        let backward = NAN_U32 - forward;
+        Some((forward as u16, backward as u16))
+    }
+
+    /// Returns the number halfway along the forward arc from `self` to `other`,
+    /// following increases even across the point of wraparound.
+    ///
+    /// This goes half of the `forward` length of [`Self::arcs()`] from `self`,
+    /// rounded down, even if the forward arc is the longer one. This is useful for
+    /// splitting a range of numbers in two.
+    ///
+    /// If one of the numbers is [`NAN`](Self::NAN), [`NAN`](Self::NAN) is returned.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 65530;
+    /// assert_eq!(a + 5, a.midpoint_forward(a + 10)); // across the wraparound
+    /// assert_eq!(a + 32772, (a + 10).midpoint_forward(a)); // along the longer arc
+    /// assert!(a.midpoint_forward(Serial::NAN).is_nan());
+    /// ```
+    #[inline]
+    pub fn midpoint_forward(self, other: Self) -> Self {
+        match self.arcs(other) {
+            Some((forward, _)) => self.wrapping_add(forward.wrapping_shr(1)),
+            None => Self::NAN,
         }
     }
 
@@ -166,6 +749,9 @@ impl Serial {
     ///
     /// If `self < other`, the result is negative,
     /// and if `self > other`, the result is positive.
+    /// This also holds at the edge of the window: if `other` is `32767` increases
+    /// ahead of `self`, the result is `-32767`, and if it is one more increase ahead,
+    /// `self` is `32767` increases ahead of `other`, and the result is `32767`.
     ///
     /// For the unsigned distance, use [`Self::dist()`].
     ///
@@ -194,12 +780,108 @@ impl Serial {
         }
     }
 
+    /// The same as [`Self::diff()`], but widened to an `i32`.
+    ///
+    /// The result still respects the window, and is in the range `-32767..=32767`.
+    /// Widening only means that you can store it, and add it to other differences,
+    /// without truncating it any further.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// let b = a + 30000;
+    /// let c = b + 30000;
+    /// assert_eq!(-60000, a.diff_into(b) + b.diff_into(c));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn diff_into(self, other: Self) -> i32 {
+        i32::from(self.diff(other))
+    }
+
+    /// Projects the [distance](Self::dist()) between two numbers onto time,
+    /// if every increase corresponds to a fixed `tick` duration.
+    ///
+    /// Returns `None` if one of the numbers is [`NAN`](Self::NAN), or if the
+    /// multiplication overflows a [`Duration`](core::time::Duration).
+    /// Like the distance, the result is the same in both directions.
+    ///
+    /// ```
+    /// use core::time::Duration;
+    /// use serial_num::Serial;
+    ///
+    /// let tick = Duration::from_millis(20);
+    /// let a = Serial::default() + 65530;
+    /// assert_eq!(Some(Duration::from_millis(200)), (a + 10).time_since(a, tick));
+    /// assert_eq!(None, a.time_since(Serial::NAN, tick));
+    /// assert_eq!(None, (a + 10).time_since(a, Duration::MAX));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn time_since(
+        self,
+        other: Self,
+        tick: core::time::Duration,
+    ) -> Option<core::time::Duration> {
+        tick.checked_mul(self.dist_exact(other)?)
+    }
+
+    /// The same as [`Self::diff()`], but only if the difference is meaningful.
+    ///
+    /// Returns `None` if one of the numbers is [`NAN`](Self::NAN), or if they are
+    /// [`WINDOW`](Self::WINDOW) apart, where the sign of the difference flips.
+    /// See [`Self::comparison_is_meaningful()`].
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some(-32766), a.diff_checked(a + 32766));
+    /// assert_eq!(None, a.diff_checked(a + 32767)); // at the window edge
+    /// assert_eq!(None, a.diff_checked(Serial::NAN));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn diff_checked(self, other: Self) -> Option<i16> {
+        self.comparison_is_meaningful(other)
+            .then(|| self.diff(other))
+    }
+
+    /// How far `self` is ahead of `anchor` (positive), or behind `anchor` (negative).
+    ///
+    /// This is the same as `self.diff(anchor)`, which includes its limits, and
+    /// its handling of [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let anchor = Serial::default();
+    /// assert_eq!(3, (anchor + 3).offset_from(anchor)); // successor
+    /// assert_eq!(-3, anchor.offset_from(anchor + 3)); // predecessor
+    /// assert_eq!(0, anchor.offset_from(anchor));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn offset_from(self, anchor: Self) -> i16 {
+        self.diff(anchor)
+    }
+
     /// Compares and returns the smaller of two numbers.
     ///
     /// The returned number is the "predecessor" of the other.
     ///
     /// If one number is [`NAN`](Self::NAN), then the other is returned.
+    /// To return [`NAN`](Self::NAN) instead, like arithmetic does,
+    /// use [`Self::min_propagate()`].
     #[inline]
+    #[cfg_attr(
+        feature = "unsafe-total-order",
+        expect(
+            clippy::same_name_method,
+            reason = "inherent method takes precedence over the trait method"
+        )
+    )]
     pub fn min(self, other: Self) -> Self {
         match self.partial_cmp(other) {
             Some(Ordering::Less) => self,
@@ -214,7 +896,16 @@ impl Serial {
     /// The returned number is the "successor" of the other.
     ///
     /// If one number is [`NAN`](Self::NAN), then the other is returned.
+    /// To return [`NAN`](Self::NAN) instead, like arithmetic does,
+    /// use [`Self::max_propagate()`].
     #[inline]
+    #[cfg_attr(
+        feature = "unsafe-total-order",
+        expect(
+            clippy::same_name_method,
+            reason = "inherent method takes precedence over the trait method"
+        )
+    )]
     pub fn max(self, other: Self) -> Self {
         match self.partial_cmp(other) {
             Some(Ordering::Greater) => self,
@@ -224,9 +915,45 @@ impl Serial {
         }
     }
 
+    /// Compares and returns the smaller of two numbers, or [`NAN`](Self::NAN)
+    /// if one of them is [`NAN`](Self::NAN).
+    ///
+    /// Unlike [`Self::min()`], which ignores a [`NAN`](Self::NAN) operand, this propagates it,
+    /// like [`Self::wrapping_add()`] and other arithmetic does.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(a, a.min(Serial::NAN));
+    /// assert_eq!(Serial::NAN, a.min_propagate(Serial::NAN));
+    /// ```
+    #[inline]
+    pub fn min_propagate(self, other: Self) -> Self {
+        if self.is_nan() || other.is_nan() {
+            return Self::NAN;
+        }
+        self.min(other)
+    }
+
+    /// Compares and returns the larger of two numbers, or [`NAN`](Self::NAN)
+    /// if one of them is [`NAN`](Self::NAN).
+    ///
+    /// Unlike [`Self::max()`], which ignores a [`NAN`](Self::NAN) operand, this propagates it,
+    /// like [`Self::wrapping_add()`] and other arithmetic does.
+    #[inline]
+    pub fn max_propagate(self, other: Self) -> Self {
+        if self.is_nan() || other.is_nan() {
+            return Self::NAN;
+        }
+        self.max(other)
+    }
+
     /// Partial comparison with wraparound.
     ///
     /// Returns `None` if one of the values is [`NAN`](Self::NAN).
+    /// For the numeric order of the counters, use [`Self::raw_cmp()`].
+    /// Note that equality (`==`) is always numeric, and does not depend on the window.
     ///
     /// Based on [RFC1982].
     ///
@@ -237,16 +964,23 @@ impl Serial {
         clippy::arithmetic_side_effects,
         reason = "overflow is handled by comparing before the arithmetic"
     )]
+    #[cfg_attr(
+        feature = "unsafe-total-order",
+        expect(
+            clippy::same_name_method,
+            reason = "inherent method takes precedence over the trait method"
+        )
+    )]
     pub fn partial_cmp(self, other: Self) -> Option<Ordering> {
         if self.is_nan() || other.is_nan() {
             return None;
         }
-        if self.0 == other.0 {
+        if self.logical() == other.logical() {
             return Some(Ordering::Equal);
         }
 
-        let a = i32::from(self.0);
-        let b = i32::from(other.0);
+        let a = i32::from(self.logical());
+        let b = i32::from(other.logical());
 
         // a < b if either:
         //  - b has the greater number and is within our window
@@ -258,25 +992,111 @@ impl Serial {
         }
     }
 
-    /// `True` if `self < other` according to [RFC1982].
+    /// The same as [`Self::partial_cmp()`].
     ///
-    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    /// This is the replacement for code that called `cmp()` when `Serial`
+    /// still implemented `PartialOrd`. Note that the result is an `Option`,
+    /// since [`NAN`](Self::NAN) cannot be compared.
+    ///
+    /// With the `unsafe-total-order` feature, this method does not exist,
+    /// so that it does not hide [`Ord::cmp()`].
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some(Ordering::Less), a.cmp(a + 1));
+    /// assert_eq!(None, a.cmp(Serial::NAN));
+    /// ```
+    #[cfg(not(feature = "unsafe-total-order"))]
     #[inline]
     #[must_use]
-    pub fn precedes(self, other: Self) -> bool {
-        self.partial_cmp(other) == Some(Ordering::Less)
+    #[expect(
+        clippy::should_implement_trait,
+        reason = "Serial does not implement Ord by default, and this returns an Option"
+    )]
+    pub fn cmp(self, other: Self) -> Option<Ordering> {
+        self.partial_cmp(other)
     }
 
-    /// `True` if `self <= other` according to [RFC1982].
+    /// Numeric comparison of the counters, without wraparound.
     ///
-    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    /// This is a total order, where [`NAN`](Self::NAN) is greater than any other number.
+    /// Unlike [`Self::partial_cmp()`], it does not consider which number is the
+    /// successor of the other, so the two disagree across the point of wraparound.
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 65534;
+    /// let b = a + 1; // wraps around to 0
+    /// assert_eq!(Some(Ordering::Less), a.partial_cmp(b));
+    /// assert_eq!(Ordering::Greater, a.raw_cmp(b));
+    /// assert_eq!(Ordering::Less, a.raw_cmp(Serial::NAN));
+    /// ```
     #[inline]
     #[must_use]
-    pub fn precedes_or_eq(self, other: Self) -> bool {
-        match self.partial_cmp(other) {
-            Some(Ordering::Less | Ordering::Equal) => true,
-            Some(Ordering::Greater) | None => false,
-        }
+    pub fn raw_cmp(self, other: Self) -> Ordering {
+        self.logical().cmp(&other.logical())
+    }
+
+    /// The same as [`Self::partial_cmp()`], but without checking for [`NAN`](Self::NAN).
+    ///
+    /// This is a fast path for numbers that are known not to be [`NAN`](Self::NAN),
+    /// for example in tight loops over validated data. The comparison is computed
+    /// without branches. If one of the numbers is [`NAN`](Self::NAN), this will not
+    /// panic or cause undefined behavior, but the result is meaningless.
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 65534;
+    /// let b = a + 1; // wraps around to 0
+    /// assert_eq!(Ordering::Less, a.cmp_unchecked(b));
+    /// assert_eq!(Ordering::Greater, b.cmp_unchecked(a));
+    /// assert_eq!(Ordering::Equal, a.cmp_unchecked(a));
+    /// ```
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "the operands are at most 65535, so none of the arithmetic overflows an i32"
+    )]
+    pub fn cmp_unchecked(self, other: Self) -> Ordering {
+        let len = i32::from(NAN_U16);
+
+        // forward offset from self to other in 0..len
+        let diff = i32::from(other.logical()) - i32::from(self.logical());
+        let offset = diff + len * i32::from(diff.is_negative());
+
+        // signed offset in -32767..=32767, where positive means self < other
+        let signed = offset - len * i32::from(offset > MID_I32);
+        let zero: i32 = 0;
+        zero.cmp(&signed)
+    }
+
+    /// `True` if `self < other` according to [RFC1982].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn precedes(self, other: Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Less)
+    }
+
+    /// `True` if `self <= other` according to [RFC1982].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn precedes_or_eq(self, other: Self) -> bool {
+        match self.partial_cmp(other) {
+            Some(Ordering::Less | Ordering::Equal) => true,
+            Some(Ordering::Greater) | None => false,
+        }
     }
 
     /// `True` if `self > other` according to [RFC1982].
@@ -300,6 +1120,152 @@ impl Serial {
         }
     }
 
+    /// `True` if neither number is [`NAN`](Self::NAN), and their [distance](Self::dist())
+    /// is less than the [`WINDOW`](Self::WINDOW).
+    ///
+    /// Use this to guard comparisons of possibly stale numbers. Numbers that are exactly
+    /// [`WINDOW`](Self::WINDOW) apart sit at the point where the comparison flips:
+    /// if either of them is off by one, [`Self::precedes()`] and [`Self::succeeds()`]
+    /// give the opposite result.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert!(a.comparison_is_meaningful(a + 32766));
+    /// assert!(!a.comparison_is_meaningful(a + 32767)); // at the flip point
+    /// assert!(!a.comparison_is_meaningful(Serial::NAN));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn comparison_is_meaningful(self, other: Self) -> bool {
+        !self.is_nan() && !other.is_nan() && self.dist(other) < Self::WINDOW
+    }
+
+    /// Classifies how close `self` is to moving out of the comparison window
+    /// that starts at `oldest_live`, the oldest number that is still in use.
+    ///
+    /// Use this to warn before comparisons with old numbers become meaningless.
+    /// The thresholds are based on the [distance](Self::dist()) relative to the
+    /// [`WINDOW`](Self::WINDOW) of `32767`:
+    /// * [`Staleness::Fresh`] below 50%, for distances `0..=16383`
+    /// * [`Staleness::Approaching`] below 90%, for distances `16384..=29490`
+    /// * [`Staleness::Critical`] from 90%, for distances `29491..=32767`
+    ///
+    /// If one of the numbers is [`NAN`](Self::NAN), the distance is the maximum,
+    /// so this is [`Staleness::Critical`].
+    ///
+    /// ```
+    /// use serial_num::{Serial, Staleness};
+    ///
+    /// let oldest_live = Serial::default();
+    /// assert_eq!(Staleness::Fresh, (oldest_live + 100).staleness(oldest_live));
+    /// assert_eq!(Staleness::Approaching, (oldest_live + 20000).staleness(oldest_live));
+    /// assert_eq!(Staleness::Critical, (oldest_live + 30000).staleness(oldest_live));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn staleness(self, oldest_live: Self) -> Staleness {
+        const APPROACHING_FROM: u16 = 16_384;
+        const CRITICAL_FROM: u16 = 29_491;
+
+        match self.dist(oldest_live) {
+            0..APPROACHING_FROM => Staleness::Fresh,
+            APPROACHING_FROM..CRITICAL_FROM => Staleness::Approaching,
+            _ => Staleness::Critical,
+        }
+    }
+
+    /// Compares two numbers like [`Self::partial_cmp()`], but only if they are
+    /// no further than `window` apart.
+    ///
+    /// Returns `None` if their [distance](Self::dist()) is greater than `window`,
+    /// or if one of them is [`NAN`](Self::NAN). A smaller `window` than the
+    /// [`WINDOW`](Self::WINDOW) of the comparison leaves a safety margin
+    /// before the comparison flips.
+    ///
+    /// ```
+    /// use core::cmp::Ordering;
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some(Ordering::Less), a.cmp_within(a + 100, 100));
+    /// assert_eq!(None, a.cmp_within(a + 101, 100));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn cmp_within(self, other: Self, window: u16) -> Option<Ordering> {
+        if self.dist(other) > window {
+            return None;
+        }
+        self.partial_cmp(other)
+    }
+
+    /// `True` if the [distance](Self::dist()) between the two numbers is at most `tolerance`.
+    ///
+    /// Two [`NAN`](Self::NAN)s are approximately equal, but [`NAN`](Self::NAN)
+    /// is never approximately equal to any other number, regardless of `tolerance`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert!(a.approx_eq(a + 3, 3));
+    /// assert!(!a.approx_eq(a + 4, 3));
+    /// assert!(Serial::NAN.approx_eq(Serial::NAN, 0));
+    /// assert!(!a.approx_eq(Serial::NAN, u16::MAX));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn approx_eq(self, other: Self, tolerance: u16) -> bool {
+        if self.is_nan() || other.is_nan() {
+            return self.is_nan() && other.is_nan();
+        }
+        self.dist(other) <= tolerance
+    }
+
+    /// `True` if `self < other` according to [RFC1982], and the [distance](Self::dist())
+    /// between them is at least `n`.
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn precedes_by_at_least(self, other: Self, n: u16) -> bool {
+        self.precedes(other) && self.dist(other) >= n
+    }
+
+    /// `True` if `self > other` according to [RFC1982], and the [distance](Self::dist())
+    /// between them is at least `n`.
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn succeeds_by_at_least(self, other: Self, n: u16) -> bool {
+        self.succeeds(other) && self.dist(other) >= n
+    }
+
+    /// `True` if `self` [precedes](Self::precedes()) the `high_water` mark, and is at most
+    /// `window` behind it.
+    ///
+    /// This is the case for an older number that was likely processed already, so
+    /// it can be dropped as a duplicate. The `high_water` mark itself is not stale.
+    /// Returns `false` if one of the numbers is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let high_water = Serial::default() + 100;
+    /// assert!((high_water.wrapping_sub(10)).is_stale(high_water, 10));
+    /// assert!(!(high_water.wrapping_sub(11)).is_stale(high_water, 10));
+    /// assert!(!high_water.is_stale(high_water, 10));
+    /// assert!(!(high_water + 1).is_stale(high_water, 10));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn is_stale(self, high_water: Self, window: u16) -> bool {
+        self.precedes(high_water) && self.dist(high_water) <= window
+    }
+
     /// Returns `self` if it's not `NAN`, otherwise returns `other`.
     #[inline]
     pub fn or(self, other: Self) -> Self {
@@ -325,16 +1291,27 @@ impl Serial {
     pub fn take(&mut self) -> Self {
         core::mem::replace(self, Self::NAN)
     }
-}
 
-impl Add<u16> for Serial {
-    type Output = Serial;
+    /// Returns `NAN` if `cond` is `true`, otherwise returns `self`.
+    #[inline]
+    pub fn nan_if(self, cond: bool) -> Self {
+        if cond {
+            Self::NAN
+        } else {
+            self
+        }
+    }
+
+    /// Returns `NAN` if `self == other`, otherwise returns `self`.
+    #[inline]
+    pub fn nan_if_eq(self, other: Self) -> Self {
+        self.nan_if(self == other)
+    }
 
     /// Addition with wraparound.
     ///
-    /// You can add any `u16` to the serial number, but be aware that due to the wraparound
-    /// semantics, adding more than `(u16::MAX-1)/2 = 32767` leads to a result that is
-    /// _less_ than `self`. Adding `u16::MAX` will wraparound to the same value.
+    /// This is the same as using the `+` operator.
+    /// Adding `u16::MAX` will wraparound to the same value.
     ///
     /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN).
     #[inline]
@@ -343,11 +1320,2866 @@ impl Add<u16> for Serial {
         reason = "the addition cannot overflow"
     )]
     #[expect(clippy::as_conversions, reason = "cannot overflow after modulo usage")]
-    fn add(self, rhs: u16) -> Self::Output {
+    pub fn wrapping_add(self, rhs: u16) -> Self {
+        if self.is_nan() {
+            return self;
+        }
+        let n = (u32::from(self.logical()) + u32::from(rhs)) % NAN_U32;
+        debug_assert!(n < NAN_U32, "addition must not result in NAN");
+        Self::from_logical(n as u16)
+    }
+
+    /// Addition with wraparound, for any type that converts into a `u16`.
+    ///
+    /// This is the same as [`Self::wrapping_add()`], but lets domain types document
+    /// what kind of offset is added.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// struct Ticks(u16);
+    ///
+    /// impl From<Ticks> for u16 {
+    ///     fn from(ticks: Ticks) -> u16 {
+    ///         ticks.0
+    ///     }
+    /// }
+    ///
+    /// let start = Serial::default();
+    /// assert_eq!(start + 3, start.advance(Ticks(3)));
+    /// ```
+    #[inline]
+    pub fn advance<T: Into<u16>>(self, ticks: T) -> Self {
+        self.wrapping_add(ticks.into())
+    }
+
+    /// Subtraction with wraparound.
+    ///
+    /// This is the inverse of [`Self::wrapping_add()`], so that `Serial(0).wrapping_sub(1)`
+    /// wraps around to the largest counter value. Subtracting `u16::MAX` will wraparound
+    /// to the same value.
+    ///
+    /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN).
+    #[inline]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "the subtraction cannot overflow after modulo usage"
+    )]
+    #[expect(clippy::as_conversions, reason = "cannot overflow after modulo usage")]
+    pub fn wrapping_sub(self, rhs: u16) -> Self {
+        if self.is_nan() {
+            return self;
+        }
+        let rem = u32::from(rhs) % NAN_U32;
+        let n = (u32::from(self.logical()) + NAN_U32 - rem) % NAN_U32;
+        Self::from_logical(n as u16)
+    }
+
+    /// Addition of a signed offset with wraparound.
+    ///
+    /// A positive `rhs` is the same as [`Self::wrapping_add()`], and a negative `rhs`
+    /// is the same as [`Self::wrapping_sub()`] with its absolute value.
+    ///
+    /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN).
+    #[inline]
+    pub fn wrapping_add_signed(self, rhs: i16) -> Self {
+        if rhs.is_negative() {
+            self.wrapping_sub(rhs.unsigned_abs())
+        } else {
+            self.wrapping_add(rhs.unsigned_abs())
+        }
+    }
+
+    /// Applies signed offsets to `self` one after another, using [`Self::wrapping_add_signed()`].
+    ///
+    /// This is the reverse of taking differences: starting at `a`, applying `b.diff(a)`
+    /// and then `c.diff(b)` lands on `c`, as long as the successive numbers are within
+    /// the comparison window of each other.
+    ///
+    /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN),
+    /// and `deltas` is not iterated at all. Since adding to a number never results
+    /// in [`NAN`](Self::NAN), this is the only case that can short-circuit.
+    ///
+    /// In iterator pipelines, this is the same as `deltas.fold(base, Serial::wrapping_add_signed)`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// let b = a + 10;
+    /// let c = b + 65530;
+    /// assert_eq!(c, a.apply_deltas([b.diff(a), c.diff(b)]));
+    /// ```
+    #[inline]
+    pub fn apply_deltas<I: IntoIterator<Item = i16>>(self, deltas: I) -> Self {
         if self.is_nan() {
             return self;
         }
-        let n = (u32::from(self.0) + u32::from(rhs)) % NAN_U32;
-        Self(n as u16)
+        deltas.into_iter().fold(self, Self::wrapping_add_signed)
+    }
+
+    /// The number that is `32767` increases ahead of `self`, which is as far apart
+    /// as numbers can be while still being comparable.
+    ///
+    /// `self` [precedes](Self::precedes) its antipode, but precedes nothing further ahead.
+    /// Since there is an odd number of `65535` numbers on the ring, the antipode
+    /// of the antipode is one behind `self`, and not `self` itself.
+    ///
+    /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(a + 32767, a.antipode());
+    /// assert!(a.precedes(a.antipode()));
+    /// assert!(a.succeeds(a.antipode() + 1));
+    /// assert_eq!(a.wrapping_sub(1), a.antipode().antipode());
+    /// ```
+    #[inline]
+    pub fn antipode(self) -> Self {
+        self.wrapping_add(MID_U16)
+    }
+
+    /// Returns `count` numbers that are spread evenly around the ring, starting at `self`.
+    ///
+    /// The numbers are `step = 65535 / count` increases apart. Since `count` might not divide
+    /// the `65535` numbers on the ring, it takes `step + 65535 % count` increases to get from
+    /// the last number back to the first. If `self.is_nan()`, the iterator is empty.
+    ///
+    /// ```
+    /// use core::num::NonZeroU16;
+    /// use serial_num::Serial;
+    ///
+    /// let start = Serial::default();
+    /// let spread: Vec<_> = start.spread(NonZeroU16::new(3).unwrap()).collect();
+    /// assert_eq!(vec![start, start + 21845, start + 43690], spread);
+    /// ```
+    #[inline]
+    #[expect(clippy::integer_division, reason = "the remainder is documented")]
+    pub fn spread(self, count: NonZeroU16) -> impl Iterator<Item = Self> {
+        let step = NAN_U16 / count;
+        let len = if self.is_nan() { 0 } else { count.get() };
+        core::iter::successors(Some(self), move |serial| Some(serial.wrapping_add(step)))
+            .take(usize::from(len))
+    }
+
+    /// Assigns this number to one of `n` shards.
+    ///
+    /// The result is deterministic, and independent of any [`Hasher`](core::hash::Hasher).
+    /// Consecutive numbers are assigned to consecutive shards.
+    ///
+    /// Returns `None` if `self` is [`NAN`](Self::NAN).
+    #[inline]
+    #[must_use]
+    pub fn shard(self, n: NonZeroU16) -> Option<u16> {
+        if self.is_nan() {
+            return None;
+        }
+        Some(self.logical() % n)
+    }
+
+    /// Hashes this number with a fixed algorithm.
+    ///
+    /// Unlike [`Hash`](core::hash::Hash), the result is the same across processes,
+    /// platforms, and versions of this crate. The algorithm is the finalizer of
+    /// [SplitMix64](https://prng.di.unimi.it/splitmix64.c), applied to the counter.
+    ///
+    /// [`NAN`](Self::NAN) hashes to `0`.
+    #[inline]
+    #[must_use]
+    pub fn stable_hash(self) -> u64 {
+        if self.is_nan() {
+            return 0;
+        }
+        let mut z = u64::from(self.logical()).wrapping_add(0x9E37_79B9_7F4A_7C15);
+        z = (z ^ z.wrapping_shr(30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+        z = (z ^ z.wrapping_shr(27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+        z ^ z.wrapping_shr(31)
+    }
+
+    /// Returns the number of serial numbers that were skipped between `prev` and `next`.
+    ///
+    /// If `next` is the successor of `prev`, the gap is `0`.
+    ///
+    /// Returns `None` if `next` does not succeed `prev`, or if one of them is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let prev = Serial::default();
+    /// assert_eq!(Some(0), Serial::sequence_gap(prev, prev + 1));
+    /// assert_eq!(Some(4), Serial::sequence_gap(prev, prev + 5));
+    /// assert_eq!(None, Serial::sequence_gap(prev + 5, prev));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn sequence_gap(prev: Self, next: Self) -> Option<u16> {
+        if !next.succeeds(prev) {
+            return None;
+        }
+        Some(next.dist(prev).saturating_sub(1))
+    }
+
+    /// Checks that `serials` are increasing, for example to verify a reconstructed log.
+    ///
+    /// If `allow_gaps` is `false`, every number must be the [successor](Self::successor)
+    /// of the one before it. If it is `true`, every number must only
+    /// [succeed](Self::succeeds) the one before it. [`NAN`](Self::NAN) is never allowed.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 65533;
+    /// assert_eq!(Ok(()), Serial::validate_increasing(&[a, a + 1, a + 2], false));
+    /// assert_eq!(Err(2), Serial::validate_increasing(&[a, a + 1, a + 3], false));
+    /// assert_eq!(Ok(()), Serial::validate_increasing(&[a, a + 1, a + 3], true));
+    /// assert_eq!(Err(1), Serial::validate_increasing(&[a, a, a + 1], true));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns the index of the first number that violates the order, or that is
+    /// [`NAN`](Self::NAN).
+    #[inline]
+    pub fn validate_increasing(serials: &[Self], allow_gaps: bool) -> Result<(), usize> {
+        if serials.first().is_some_and(|first| first.is_nan()) {
+            return Err(0);
+        }
+        for (index, pair) in serials.windows(2).enumerate() {
+            let &[prev, next] = pair else {
+                continue;
+            };
+            let increasing = if allow_gaps {
+                next.succeeds(prev)
+            } else {
+                next.is_next_after(prev)
+            };
+            if !increasing {
+                return Err(index.saturating_add(1));
+            }
+        }
+        Ok(())
+    }
+
+    /// Returns the length of the contiguous run at the start of `received`,
+    /// that begins with `start`, and where every number is the
+    /// [successor](Self::successor) of the one before it.
+    ///
+    /// This is how many received numbers can be delivered in order. The run ends at
+    /// the first gap, or at the first [`NAN`](Self::NAN). If `start` is
+    /// [`NAN`](Self::NAN), the run is empty.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let start = Serial::default() + 65533;
+    /// let received = [start, start + 1, start + 2, start + 4];
+    /// assert_eq!(3, Serial::contiguous_run(start, &received)); // across the wraparound
+    /// assert_eq!(0, Serial::contiguous_run(start + 1, &received));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn contiguous_run(start: Self, received: &[Self]) -> u16 {
+        let mut expected = start;
+        let mut len: u16 = 0;
+        for &serial in received {
+            if serial.is_nan() || serial != expected || len == u16::MAX {
+                break;
+            }
+            expected.increase();
+            len = len.saturating_add(1);
+        }
+        len
+    }
+
+    /// Returns the number of increases it takes to get from `other` to `self`.
+    ///
+    /// Returns `None` if `self` precedes `other`, or if one of them is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// assert_eq!(Some(5), (a + 5).checked_sub(a));
+    /// assert_eq!(Some(0), a.checked_sub(a));
+    /// assert_eq!(None, a.checked_sub(a + 5));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn checked_sub(self, other: Self) -> Option<u16> {
+        if !self.succeeds_or_eq(other) {
+            return None;
+        }
+        Some(self.dist(other))
+    }
+
+    /// Returns how far `self` is into the window that starts at `low`.
+    ///
+    /// The position of `low` itself is `0`, and the furthest position is
+    /// [`WINDOW`](Self::WINDOW). This is the same as `self.checked_sub(low)`.
+    ///
+    /// Returns `None` if `self` precedes `low`, or if one of them is [`NAN`](Self::NAN).
+    ///
+    /// If `low` marks the start of a logical cycle, or epoch, this is the 0-based index
+    /// of `self` within that cycle, which can be used to bucket numbers into slots.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let low = Serial::default() + 65530;
+    /// assert_eq!(Some(0), low.window_position(low));
+    /// assert_eq!(Some(10), (low + 10).window_position(low));
+    /// assert_eq!(None, low.wrapping_sub(1).window_position(low));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn window_position(self, low: Self) -> Option<u16> {
+        self.checked_sub(low)
+    }
+
+    /// Expresses each of the `serials` by its [position](Self::window_position())
+    /// in the window that starts at `anchor`, for example to plot them on a readable axis.
+    ///
+    /// `out[i]` is set to the position of `serials[i]`, so the output stays monotonic
+    /// when the numbers straddle the point of wraparound. Numbers that precede the
+    /// `anchor`, and [`NAN`](Self::NAN), are set to `u16::MAX`, which is never
+    /// a valid position. If the slices have different lengths, only the shorter
+    /// length is filled.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let anchor = Serial::default() + 65533;
+    /// let serials = [anchor, anchor + 1, anchor + 2, anchor + 3, Serial::NAN];
+    /// let mut out = [0; 5];
+    /// Serial::normalize_against(&serials, anchor, &mut out);
+    /// assert_eq!([0, 1, 2, 3, u16::MAX], out);
+    /// ```
+    #[inline]
+    pub fn normalize_against(serials: &[Self], anchor: Self, out: &mut [u16]) {
+        for (position, serial) in out.iter_mut().zip(serials) {
+            *position = serial.window_position(anchor).unwrap_or(u16::MAX);
+        }
+    }
+
+    /// Validates and measures the range from `low` to `high`.
+    ///
+    /// Returns the [distance](Self::dist()) between `low` and `high`, if `high`
+    /// succeeds or equals `low`. Since this is decided by comparing the two
+    /// numbers, the result also never exceeds the comparison window of `32767`.
+    ///
+    /// Returns `None` if `high` precedes `low`, or if one of them is [`NAN`](Self::NAN).
+    ///
+    /// This is the same as `high.checked_sub(low)`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let low = Serial::default();
+    /// assert_eq!(Some(10), Serial::range_len(low, low + 10));
+    /// assert_eq!(None, Serial::range_len(low + 10, low));
+    /// assert_eq!(None, Serial::range_len(low, low + 40000)); // nonsensical range
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn range_len(low: Self, high: Self) -> Option<u16> {
+        high.checked_sub(low)
+    }
+
+    /// Divides the ring into the numbers that precede `pivot`, and those that
+    /// succeed or equal it, as `(before, after)`.
+    ///
+    /// `after` starts at `pivot`, and covers the full [`WINDOW`](Self::WINDOW) after it.
+    /// `before` covers the remaining numbers, up to the predecessor of `pivot`.
+    /// Every number other than [`NAN`](Self::NAN) is in exactly one of them.
+    ///
+    /// Returns `None` if `pivot` is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let pivot = Serial::default();
+    /// let (before, after) = Serial::ring_halves(pivot).unwrap();
+    /// assert!(after.contains(pivot) && after.contains(pivot + 32767));
+    /// assert!(before.contains(pivot + 32768) && before.contains(pivot.wrapping_sub(1)));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn ring_halves(pivot: Self) -> Option<(SerialRange<SENTINEL>, SerialRange<SENTINEL>)> {
+        let after = SerialRange::new(pivot, pivot.wrapping_add(Self::WINDOW))?;
+        let before = SerialRange::new(after.last().successor(), pivot.wrapping_sub(1))?;
+        Some((before, after))
+    }
+
+    /// Snaps `self` to the edge of the [`WINDOW`](Self::WINDOW) around `anchor`,
+    /// if it is further away.
+    ///
+    /// This is the same as [`Self::clamp_to_window_of()`] with the full window.
+    /// Since the [distance](Self::dist()) is measured along the shorter arc, it is
+    /// never larger than the window, so this returns `self`: a number at
+    /// `WINDOW + 1` steps ahead of `anchor` is already at the edge of the window
+    /// behind it. Use [`Self::clamp_to_window_of()`] to reject numbers that are
+    /// closer than that.
+    ///
+    /// If `self` or `anchor` is [`NAN`](Self::NAN), `self` is returned.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let anchor = Serial::default() + 1000;
+    /// let edge = anchor + Serial::WINDOW;
+    /// assert_eq!(edge, edge.clamp_to_window(anchor));
+    /// assert_eq!(edge + 1, (edge + 1).clamp_to_window(anchor)); // the edge behind
+    /// assert!((edge + 1).precedes(anchor));
+    /// ```
+    #[inline]
+    pub fn clamp_to_window(self, anchor: Self) -> Self {
+        self.clamp_to_window_of(anchor, Self::WINDOW)
+    }
+
+    /// Snaps `self` to the edge of a `window` around `anchor`, if it is further away.
+    ///
+    /// If the [distance](Self::dist()) of `self` to `anchor` is at most `window`, `self`
+    /// is returned. Otherwise, the number `window` steps ahead of `anchor` is returned
+    /// if `self` succeeds `anchor`, and the number `window` steps behind it if `self`
+    /// precedes `anchor`.
+    ///
+    /// This is a defensive measure for untrusted input, that is implausibly far away
+    /// from the current state.
+    ///
+    /// If `self` or `anchor` is [`NAN`](Self::NAN), `self` is returned.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let anchor = Serial::default() + 1000;
+    /// assert_eq!(anchor + 10, (anchor + 10).clamp_to_window_of(anchor, 100));
+    /// assert_eq!(anchor + 100, (anchor + 5000).clamp_to_window_of(anchor, 100));
+    /// assert_eq!(anchor.wrapping_sub(100), anchor.wrapping_sub(5000).clamp_to_window_of(anchor, 100));
+    /// ```
+    #[inline]
+    pub fn clamp_to_window_of(self, anchor: Self, window: u16) -> Self {
+        if self.is_nan() || anchor.is_nan() || self.dist(anchor) <= window {
+            return self;
+        }
+        if self.succeeds(anchor) {
+            anchor.wrapping_add(window)
+        } else {
+            anchor.wrapping_sub(window)
+        }
+    }
+
+    /// Binary searches a sorted window of serial numbers for `target`.
+    ///
+    /// Returns `Ok` with the index of `target` if it is found, or `Err` with the index
+    /// where it could be inserted to keep the window sorted, like [`slice::binary_search()`].
+    ///
+    /// `sorted` must be sorted in wraparound order, where every number
+    /// [precedes](Self::precedes) the next, and the whole window must fit into the
+    /// [`WINDOW`](Self::WINDOW). The window may straddle the point of wraparound.
+    /// Numbers are ordered by how far they are ahead of the first number in `sorted`,
+    /// so `target` is inserted at the front if it precedes the first number.
+    /// [`NAN`](Self::NAN) is sorted after all other numbers.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let start = Serial::default() + 65530;
+    /// let sorted = [start, start + 2, start + 4, start + 6]; // wraps around
+    /// assert_eq!(Ok(2), Serial::search_window(&sorted, start + 4));
+    /// assert_eq!(Err(3), Serial::search_window(&sorted, start + 5));
+    /// assert_eq!(Err(0), Serial::search_window(&sorted, start.wrapping_sub(1)));
+    /// ```
+    ///
+    /// # Errors
+    /// Returns `Err` with the insertion point if `target` is not found.
+    #[inline]
+    pub fn search_window(sorted: &[Self], target: Self) -> Result<usize, usize> {
+        let Some(&anchor) = sorted.first() else {
+            return Err(0);
+        };
+        if target.precedes(anchor) {
+            return Err(0);
+        }
+        let offset = |serial: Self| anchor.arcs(serial).map_or(u16::MAX, |(forward, _)| forward);
+        sorted.binary_search_by_key(&offset(target), |&serial| offset(serial))
+    }
+
+    /// Returns the rank of `self` among the numbers in `window`, where `0` is the earliest.
+    ///
+    /// The rank is the number of entries in `window` that [precede](Self::precedes())
+    /// `self`. The slice does not have to be sorted, but all its numbers must fit into one
+    /// comparison window. [`NAN`](Self::NAN) entries are not counted.
+    ///
+    /// Returns `None` if `self` is not in `window`, or if `self` is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default() + 65533;
+    /// let window = [a + 3, a, Serial::NAN, a + 1];
+    /// assert_eq!(Some(0), a.rank_in(&window));
+    /// assert_eq!(Some(2), (a + 3).rank_in(&window)); // across the wraparound
+    /// assert_eq!(None, (a + 2).rank_in(&window));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn rank_in(self, window: &[Self]) -> Option<usize> {
+        if self.is_nan() || !window.contains(&self) {
+            return None;
+        }
+        Some(window.iter().filter(|serial| serial.precedes(self)).count())
+    }
+
+    /// Returns the smallest window `(low, high)` that covers all of the given ranges.
+    ///
+    /// Every range `(low, high)` includes both ends, and `low` must
+    /// [precede or equal](Self::precedes_or_eq) `high`. The ranges may overlap, be
+    /// disjoint, or straddle the point of wraparound.
+    ///
+    /// Returns `None` if `ranges` is empty, if any range contains [`NAN`](Self::NAN)
+    /// or is reversed, or if the covering window would be larger than the
+    /// [`WINDOW`](Self::WINDOW).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// let ranges = [(a + 10, a + 20), (a + 65530, a + 2)];
+    /// assert_eq!(Some((a + 65530, a + 20)), Serial::bounding_window(&ranges));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn bounding_window(ranges: &[(Self, Self)]) -> Option<(Self, Self)> {
+        if ranges.is_empty() || ranges.iter().any(|&(low, high)| !low.precedes_or_eq(high)) {
+            return None;
+        }
+        // The smallest window starts at one of the lows. For each of them,
+        // find out how far the window would need to reach.
+        let span_from = |start: Self| {
+            ranges.iter().try_fold(0, |span: u16, &(low, high)| {
+                let (to_low, _) = start.arcs(low)?;
+                let (to_high, _) = start.arcs(high)?;
+                // a range that contains `start` (but doesn't start there) cannot be covered
+                (to_low <= to_high).then_some(span.max(to_high))
+            })
+        };
+        ranges
+            .iter()
+            .filter_map(|&(start, _)| span_from(start).map(|span| (start, span)))
+            .min_by_key(|&(_, span)| span)
+            .filter(|&(_, span)| span <= Self::WINDOW)
+            .map(|(start, span)| (start, start.wrapping_add(span)))
+    }
+
+    /// Returns the serial number with the smallest [distance](Self::dist()) to `reference`.
+    ///
+    /// If two numbers have the same distance, the predecessor of the two is returned.
+    /// [`NAN`](Self::NAN) values in `iter` are skipped.
+    ///
+    /// Returns `None` if `iter` has no value other than [`NAN`](Self::NAN),
+    /// or if `reference` is [`NAN`](Self::NAN).
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let reference = Serial::default();
+    /// let candidates = [reference + 10, reference + 65530, reference + 20];
+    /// assert_eq!(Some(reference + 65530), Serial::closest_to(reference, candidates));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn closest_to<I: IntoIterator<Item = Self>>(reference: Self, iter: I) -> Option<Self> {
+        if reference.is_nan() {
+            return None;
+        }
+        iter.into_iter()
+            .filter(|num| !num.is_nan())
+            .reduce(
+                |closest, num| match closest.dist(reference).cmp(&num.dist(reference)) {
+                    Ordering::Less => closest,
+                    Ordering::Greater => num,
+                    Ordering::Equal => closest.min(num),
+                },
+            )
+    }
+
+    /// Derives a serial number from a hash, which is never [`NAN`](Self::NAN).
+    ///
+    /// The hash is reduced modulo the `65535` numbers on the ring. Unlike
+    /// [`Self::from_low_bits()`], this never produces [`NAN`](Self::NAN), and uses
+    /// all bits of the hash.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Serial::default() + 1, Serial::from_hash(65536));
+    /// assert!(!Serial::from_hash(u64::MAX).is_nan());
+    /// ```
+    #[inline]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        reason = "the remainder fits into a u16"
+    )]
+    pub fn from_hash(h: u64) -> Self {
+        Self::from_logical((h % u64::from(NAN_U32)) as u16)
+    }
+
+    /// Parses a serial number from a string in the given `radix`, with an optional
+    /// `0x`, `0o`, or `0b` prefix for radix 16, 8, or 2. The prefixes are matched
+    /// case-insensitively, so `0X`, `0O`, and `0B` work as well.
+    ///
+    /// The number is parsed like [`u16::from_str_radix()`], and `0xFFFF`
+    /// becomes [`NAN`](Self::NAN). Unlike [`u16::from_str_radix()`], a leading `+`
+    /// is rejected, since serial numbers have no sign. Unlike the
+    /// [`FromStr`](core::str::FromStr) implementation, this does not accept `NaN`,
+    /// since that could be a number in a large enough radix.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Ok(Serial::default() + 0x1A2B), Serial::from_str_radix("0x1A2B", 16));
+    /// assert_eq!(Ok(Serial::default() + 0x1A2B), Serial::from_str_radix("0X1A2B", 16));
+    /// assert_eq!(Ok(Serial::default() + 5), Serial::from_str_radix("0b101", 2));
+    /// assert_eq!(Ok(Serial::NAN), Serial::from_str_radix("FFFF", 16));
+    /// assert!(Serial::from_str_radix("1_0000", 16).is_err());
+    /// assert!(Serial::from_str_radix("+5", 10).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ParseSerialError`] if `s` is not a number in the range of `u16`,
+    /// or if `radix` is not in the range `2..=36`.
+    #[inline]
+    pub fn from_str_radix(s: &str, radix: u32) -> Result<Self, ParseSerialError> {
+        if !(2..=36).contains(&radix) {
+            return Err(ParseSerialError);
+        }
+        let prefix = match radix {
+            16 => Some("0x"),
+            8 => Some("0o"),
+            2 => Some("0b"),
+            _ => None,
+        };
+        let digits = prefix
+            .filter(|pre| {
+                s.get(..pre.len())
+                    .is_some_and(|head| head.eq_ignore_ascii_case(pre))
+            })
+            .and_then(|pre| s.get(pre.len()..))
+            .unwrap_or(s);
+        if digits.starts_with('+') {
+            return Err(ParseSerialError);
+        }
+        u16::from_str_radix(digits, radix)
+            .map(Self::from_logical)
+            .map_err(|_err| ParseSerialError)
+    }
+
+    /// Parses a decimal number like the [`FromStr`](core::str::FromStr) implementation,
+    /// but accepts more ways of saying "no serial number".
+    ///
+    /// Besides `NaN`, the empty string, `-`, `none`, and `null` are parsed as
+    /// [`NAN`](Self::NAN). Apart from `-`, these tokens are matched case-insensitively.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Ok(Serial::default() + 42), Serial::parse_lenient("42"));
+    /// assert_eq!(Ok(Serial::NAN), Serial::parse_lenient(""));
+    /// assert_eq!(Ok(Serial::NAN), Serial::parse_lenient("-"));
+    /// assert_eq!(Ok(Serial::NAN), Serial::parse_lenient("None"));
+    /// assert!(Serial::parse_lenient("nil").is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`ParseSerialError`] if `s` is neither one of these tokens,
+    /// nor a decimal number in the range of `u16`.
+    #[inline]
+    pub fn parse_lenient(s: &str) -> Result<Self, ParseSerialError> {
+        const NAN_TOKENS: [&str; 5] = ["", "-", "nan", "none", "null"];
+        if NAN_TOKENS.iter().any(|token| s.eq_ignore_ascii_case(token)) {
+            return Ok(Self::NAN);
+        }
+        Self::from_str_radix(s, 10)
+    }
+
+    /// Truncates a wider counter, like a timestamp, to a serial number, by taking
+    /// its lowest 16 bits.
+    ///
+    /// If the lowest 16 bits are all ones (`0xFFFF`), the result is [`NAN`](Self::NAN).
+    /// Note that this means the serial numbers of consecutive counters do not wrap
+    /// around at the same point as [`Self::increase()`] does.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Serial::default(), Serial::from_low_bits(0x1_0000));
+    /// assert_eq!(Serial::default() + 42, Serial::from_low_bits(0xABCD_002A));
+    /// assert!(Serial::from_low_bits(0x1_FFFF).is_nan());
+    /// ```
+    #[inline]
+    #[expect(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        reason = "truncation is intended"
+    )]
+    pub fn from_low_bits(n: u64) -> Self {
+        Self::from_logical(n as u16)
+    }
+
+    /// Packs `self` with an `epoch` that you track yourself, like the number of times
+    /// the counter wrapped around, into a `u32` that grows monotonically across wraps.
+    ///
+    /// The `epoch` is in the upper 16 bits, and the counter is in the lower 16 bits,
+    /// with `0xFFFF` for [`NAN`](Self::NAN).
+    ///
+    /// Use [`Self::unpack_with_epoch()`] for the reverse.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let serial = Serial::default() + 0x1234;
+    /// assert_eq!(0x0002_1234, serial.pack_with_epoch(2));
+    /// assert_eq!(0x0002_FFFF, Serial::NAN.pack_with_epoch(2));
+    /// ```
+    #[inline]
+    #[must_use]
+    pub fn pack_with_epoch(self, epoch: u16) -> u32 {
+        u32::from(epoch).wrapping_shl(u16::BITS) | u32::from(self.logical())
+    }
+
+    /// Unpacks an epoch and a serial number from a `u32`, as `(epoch, serial)`.
+    ///
+    /// This is the reverse of [`Self::pack_with_epoch()`].
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!((2, Serial::default() + 0x1234), Serial::unpack_with_epoch(0x0002_1234));
+    /// assert_eq!((2, Serial::NAN), Serial::unpack_with_epoch(0x0002_FFFF));
+    /// ```
+    #[inline]
+    #[expect(
+        clippy::as_conversions,
+        clippy::cast_possible_truncation,
+        reason = "truncation to the lower 16 bits is intended"
+    )]
+    pub fn unpack_with_epoch(packed: u32) -> (u16, Self) {
+        let epoch = packed.wrapping_shr(u16::BITS) as u16;
+        (epoch, Self::from_logical(packed as u16))
+    }
+
+    /// Writes the counter of `self` as a decimal number into `buf`, and returns the
+    /// written part of it.
+    ///
+    /// [`NAN`](Self::NAN) is written as `NaN`. This only uses `core`, and needs at most
+    /// five bytes, which makes it useful to render numbers without `alloc`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut buf = [0; 5];
+    /// assert_eq!(Ok("42"), (Serial::default() + 42).format_into(&mut buf));
+    /// assert_eq!(Ok("NaN"), Serial::NAN.format_into(&mut buf));
+    /// assert!((Serial::default() + 420).format_into(&mut buf[..2]).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`BufferTooSmall`] if `buf` is too small to hold the text.
+    #[inline]
+    #[expect(
+        clippy::arithmetic_side_effects,
+        clippy::as_conversions,
+        reason = "a decimal digit fits into a u8, and cannot overflow"
+    )]
+    pub fn format_into(self, buf: &mut [u8]) -> Result<&str, BufferTooSmall> {
+        let mut digits: [u8; 5] = [b'0'; 5];
+        let text: &[u8] = if self.is_nan() {
+            b"NaN"
+        } else {
+            let mut n = self.logical();
+            let mut len = 0;
+            for digit in digits.iter_mut().rev() {
+                *digit = b'0' + (n % 10) as u8;
+                n /= 10;
+                len += 1;
+                if n == 0 {
+                    break;
+                }
+            }
+            digits.get(digits.len() - len..).unwrap_or_default()
+        };
+        let out = buf.get_mut(..text.len()).ok_or(BufferTooSmall)?;
+        out.copy_from_slice(text);
+        let written: &[u8] = out;
+        // the text is ASCII, so the first chunk of valid UTF-8 is all of it
+        Ok(written
+            .utf8_chunks()
+            .next()
+            .map(|chunk| chunk.valid())
+            .unwrap_or_default())
+    }
+
+    /// Writes the counter of `self` as a decimal number into a fixed-size field,
+    /// and returns the number of written bytes.
+    ///
+    /// This is [`Self::format_into()`] for a buffer that always fits, so it cannot fail.
+    /// [`NAN`](Self::NAN) is written as `NaN`, and the bytes after the returned length
+    /// are left unchanged.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let mut buf = [b' '; 5];
+    /// assert_eq!(5, (Serial::default() + 65534).to_fixed_decimal(&mut buf));
+    /// assert_eq!(b"65534", &buf);
+    /// assert_eq!(3, Serial::NAN.to_fixed_decimal(&mut buf));
+    /// assert_eq!(b"NaN34", &buf);
+    /// ```
+    #[inline]
+    pub fn to_fixed_decimal(self, buf: &mut [u8; 5]) -> usize {
+        self.format_into(buf).map_or(0, str::len)
+    }
+
+    /// Converts a signed integer from a foreign interface, where negative numbers
+    /// like `-1` mean "no serial number".
+    ///
+    /// Negative numbers become [`NAN`](Self::NAN), and `0..=65534` are taken as the counter.
+    ///
+    /// Use [`Self::to_ffi()`] for the reverse.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(Ok(Serial::default() + 42), Serial::from_ffi(42));
+    /// assert_eq!(Ok(Serial::NAN), Serial::from_ffi(-1));
+    /// assert!(Serial::from_ffi(65535).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`FfiRangeError`] if `n` is larger than `65534`.
+    #[inline]
+    pub fn from_ffi(n: i32) -> Result<Self, FfiRangeError> {
+        if n.is_negative() {
+            return Ok(Self::NAN);
+        }
+        match u16::try_from(n) {
+            Ok(counter) if counter != NAN_U16 => Ok(Self::from_logical(counter)),
+            _ => Err(FfiRangeError),
+        }
+    }
+
+    /// Converts to a signed integer for a foreign interface, where [`NAN`](Self::NAN)
+    /// is represented by `-1`.
+    ///
+    /// Use [`Self::from_ffi()`] for the reverse.
+    #[inline]
+    #[must_use]
+    pub fn to_ffi(self) -> i32 {
+        if self.is_nan() {
+            -1
+        } else {
+            i32::from(self.logical())
+        }
+    }
+
+    /// Packs serial numbers into a buffer of little-endian `u16`s.
+    ///
+    /// The numbers are packed with their raw value, so [`NAN`](Self::NAN) is packed as the
+    /// `SENTINEL`, which is `0xFFFF` for [`Serial`].
+    ///
+    /// Use [`Self::unpack_le()`] for the reverse.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::little_endian_bytes,
+        reason = "the packed format is little-endian"
+    )]
+    pub fn pack_le(serials: &[Self]) -> Vec<u8> {
+        serials.iter().flat_map(|num| num.0.to_le_bytes()).collect()
+    }
+
+    /// Unpacks serial numbers from a buffer of little-endian `u16`s.
+    ///
+    /// The `SENTINEL`, which is `0xFFFF` for [`Serial`], is unpacked as [`NAN`](Self::NAN).
+    ///
+    /// This is the reverse of [`Self::pack_le()`].
+    ///
+    /// # Errors
+    /// Returns [`OddLengthError`] if `bytes` does not have an even length.
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[expect(
+        clippy::little_endian_bytes,
+        reason = "the packed format is little-endian"
+    )]
+    pub fn unpack_le(bytes: &[u8]) -> Result<Vec<Self>, OddLengthError> {
+        let chunks = bytes.chunks_exact(2);
+        if !chunks.remainder().is_empty() {
+            return Err(OddLengthError);
+        }
+        Ok(chunks
+            .map(|chunk| match *chunk {
+                [lo, hi] => Self(u16::from_le_bytes([lo, hi])),
+                _ => Self::NAN,
+            })
+            .collect())
+    }
+
+    /// Compresses serial numbers into ranges of consecutive numbers, for example
+    /// to acknowledge them in a compact form.
+    ///
+    /// The numbers are sorted in wraparound order, starting at the [smallest](Self::min())
+    /// of them, so they must all fit into one comparison window. Duplicates are merged,
+    /// and [`NAN`](Self::NAN) is ignored.
+    ///
+    /// ```
+    /// use serial_num::{Serial, SerialRange};
+    ///
+    /// let start = Serial::default() + 65533;
+    /// let ranges = Serial::coalesce(&[start + 2, start, start + 1, start + 5]);
+    /// assert_eq!(
+    ///     vec![
+    ///         SerialRange::new(start, start + 2).unwrap(), // wraps around
+    ///         SerialRange::new(start + 5, start + 5).unwrap(),
+    ///     ],
+    ///     ranges,
+    /// );
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn coalesce(serials: &[Self]) -> Vec<SerialRange<SENTINEL>> {
+        let earliest = serials
+            .iter()
+            .fold(Self::NAN, |earliest, &serial| earliest.min(serial));
+
+        let mut sorted: Vec<Self> = serials
+            .iter()
+            .copied()
+            .filter(|serial| !serial.is_nan())
+            .collect();
+        sorted.sort_unstable_by_key(|serial| serial.dist(earliest));
+
+        let mut ranges: Vec<SerialRange<SENTINEL>> = Vec::new();
+        for serial in sorted {
+            match ranges.last_mut() {
+                Some(range) if serial == range.last => {}
+                Some(range) if serial.is_next_after(range.last) => range.last = serial,
+                _ => ranges.push(SerialRange {
+                    first: serial,
+                    last: serial,
+                }),
+            }
+        }
+        ranges
+    }
+
+    /// Encodes serial numbers as the first number, and the [differences](Self::diff())
+    /// between successive numbers, which are usually small and compress well.
+    ///
+    /// Use [`Self::decode_deltas()`] to get the numbers back. For non-empty slices,
+    /// this round trip is lossless, since every difference fits in `-32767..=32767`:
+    /// * [`NAN`](Self::NAN) encodes as `i16::MIN`, and does not break the chain:
+    ///   the next difference is taken from the last number before it.
+    /// * If there is no number before it that is not [`NAN`](Self::NAN),
+    ///   the difference is taken from [`ZERO`](Self::ZERO).
+    /// * An empty slice encodes as [`NAN`](Self::NAN) without any differences.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let start = Serial::default() + 65533;
+    /// let serials = [start, start + 1, start + 3, Serial::NAN, start + 2];
+    ///
+    /// let (base, deltas) = Serial::encode_deltas(&serials);
+    /// assert_eq!(start, base);
+    /// assert_eq!(vec![1, 2, i16::MIN, -1], deltas); // across the wraparound
+    /// assert_eq!(serials.to_vec(), Serial::decode_deltas(base, &deltas));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    pub fn encode_deltas(serials: &[Self]) -> (Self, Vec<i16>) {
+        let Some((&base, rest)) = serials.split_first() else {
+            return (Self::NAN, Vec::new());
+        };
+
+        let mut last = base;
+        let deltas = rest
+            .iter()
+            .map(|&serial| {
+                if serial.is_nan() {
+                    return i16::MIN;
+                }
+                let delta = serial.diff(last.or_default());
+                last = serial;
+                delta
+            })
+            .collect();
+        (base, deltas)
+    }
+
+    /// Decodes serial numbers from the output of [`Self::encode_deltas()`].
+    ///
+    /// The result starts with `base`, followed by one number for each delta.
+    /// A delta of `i16::MIN` decodes as [`NAN`](Self::NAN), and the next delta
+    /// is applied to the last number before it. If there is no such number,
+    /// the delta is applied to [`ZERO`](Self::ZERO).
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn decode_deltas(base: Self, deltas: &[i16]) -> Vec<Self> {
+        let mut last = base;
+        core::iter::once(base)
+            .chain(deltas.iter().map(|&delta| {
+                if delta == i16::MIN {
+                    return Self::NAN;
+                }
+                last = last.or_default().wrapping_add_signed(delta);
+                last
+            }))
+            .collect()
+    }
+
+    /// Counts the [distances](Self::dist()) between consecutive numbers into buckets,
+    /// for example to spot packet loss in a capture.
+    ///
+    /// The histogram has `2^bucket_bits` buckets of equal width, that together cover
+    /// all distances from `0` to `32767`. Since distances have 15 bits, a `bucket_bits`
+    /// of `15` or more gives one bucket per distance, and `0` gives a single bucket.
+    /// Pairs that include [`NAN`](Self::NAN) are skipped.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let a = Serial::default();
+    /// let serials = [a, a + 1, a + 2, a + 20000, Serial::NAN, a];
+    ///
+    /// // buckets of width 8192: two small steps, and one large gap
+    /// assert_eq!(vec![2, 0, 1, 0], Serial::distance_histogram(&serials, 2));
+    /// ```
+    #[cfg(feature = "alloc")]
+    #[inline]
+    #[must_use]
+    pub fn distance_histogram(serials: &[Self], bucket_bits: u8) -> Vec<u32> {
+        const DIST_BITS: u32 = u16::BITS.saturating_sub(1);
+        let bits = u32::from(bucket_bits).min(DIST_BITS);
+        let shift = DIST_BITS.saturating_sub(bits);
+
+        let mut buckets = alloc::vec![0; usize::pow(2, bits)];
+        for pair in serials.windows(2) {
+            let &[prev, next] = pair else {
+                continue;
+            };
+            if prev.is_nan() || next.is_nan() {
+                continue;
+            }
+            let bucket = usize::from(prev.dist(next).wrapping_shr(shift));
+            if let Some(count) = buckets.get_mut(bucket) {
+                *count = u32::saturating_add(*count, 1);
+            }
+        }
+        buckets
+    }
+
+    /// Reinterprets bytes as a serial number, rejecting the [`NAN`](Self::NAN) pattern.
+    ///
+    /// Since every `u16` is a valid bit pattern of [`Serial`], `bytemuck` casts
+    /// like `bytemuck::checked::try_from_bytes()` silently turn the `SENTINEL`,
+    /// which is `0xFFFF` for [`Serial`], into [`NAN`](Self::NAN). Use this for data
+    /// from untrusted memory, where that pattern should be flagged instead.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let bytes = bytemuck::bytes_of(&42_u16);
+    /// assert_eq!(Ok(&(Serial::default() + 42)), Serial::try_from_bytes_non_nan(bytes));
+    ///
+    /// let bytes = bytemuck::bytes_of(&u16::MAX);
+    /// assert!(Serial::try_from_bytes_non_nan(bytes).is_err());
+    /// ```
+    ///
+    /// # Errors
+    /// Returns [`CheckedCastError::InvalidBitPattern`] for the [`NAN`](Self::NAN) pattern,
+    /// and [`CheckedCastError::PodCastError`] if `bytes` has the wrong size or alignment.
+    ///
+    /// [`CheckedCastError::InvalidBitPattern`]: bytemuck::checked::CheckedCastError::InvalidBitPattern
+    /// [`CheckedCastError::PodCastError`]: bytemuck::checked::CheckedCastError::PodCastError
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn try_from_bytes_non_nan(
+        bytes: &[u8],
+    ) -> Result<&Self, bytemuck::checked::CheckedCastError> {
+        let serial: &Self = bytemuck::try_from_bytes(bytes)?;
+        if serial.is_nan() {
+            return Err(bytemuck::checked::CheckedCastError::InvalidBitPattern);
+        }
+        Ok(serial)
+    }
+
+    /// Reinterprets bytes as a slice of serial numbers, rejecting the
+    /// [`NAN`](Self::NAN) pattern.
+    ///
+    /// This is the slice version of [`Self::try_from_bytes_non_nan()`].
+    ///
+    /// # Errors
+    /// Returns [`CheckedCastError::InvalidBitPattern`] if any number has the
+    /// [`NAN`](Self::NAN) pattern, and [`CheckedCastError::PodCastError`] if `bytes`
+    /// has the wrong size or alignment.
+    ///
+    /// [`CheckedCastError::InvalidBitPattern`]: bytemuck::checked::CheckedCastError::InvalidBitPattern
+    /// [`CheckedCastError::PodCastError`]: bytemuck::checked::CheckedCastError::PodCastError
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    pub fn try_cast_slice_non_nan(
+        bytes: &[u8],
+    ) -> Result<&[Self], bytemuck::checked::CheckedCastError> {
+        let serials: &[Self] = bytemuck::try_cast_slice(bytes)?;
+        if serials.iter().any(|serial| serial.is_nan()) {
+            return Err(bytemuck::checked::CheckedCastError::InvalidBitPattern);
+        }
+        Ok(serials)
+    }
+
+    /// Reinterprets a slice of serial numbers as their raw `u16` values, without copying.
+    ///
+    /// [`NAN`](Self::NAN) is represented by the `SENTINEL`, which is `0xFFFF` for [`Serial`].
+    /// This is useful to run bulk numeric operations on a contiguous `[u16]`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let serials = [Serial::default(), Serial::default() + 1, Serial::NAN];
+    /// assert_eq!([0, 1, 0xFFFF], Serial::as_u16_slice(&serials));
+    /// ```
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    #[must_use]
+    pub fn as_u16_slice(serials: &[Self]) -> &[u16] {
+        bytemuck::cast_slice(serials)
+    }
+
+    /// Reinterprets a mutable slice of serial numbers as their raw `u16` values,
+    /// without copying.
+    ///
+    /// Every `u16` is a valid serial number, where the `SENTINEL` is [`NAN`](Self::NAN).
+    #[cfg(feature = "bytemuck")]
+    #[inline]
+    #[must_use]
+    pub fn as_u16_slice_mut(serials: &mut [Self]) -> &mut [u16] {
+        bytemuck::cast_slice_mut(serials)
+    }
+
+    /// Converts serial numbers into their raw `u16` values.
+    ///
+    /// [`NAN`](Self::NAN) is represented by the `SENTINEL`, which is `0xFFFF` for [`Serial`].
+    #[cfg(all(feature = "bytemuck", feature = "alloc"))]
+    #[inline]
+    #[must_use]
+    pub fn into_u16_vec(serials: Vec<Self>) -> Vec<u16> {
+        // collecting into a vector of the same layout reuses the allocation
+        serials.into_iter().map(|serial| serial.0).collect()
+    }
+
+    /// Converts `self` to a JSON value, without going through a string.
+    ///
+    /// A number becomes a [`Value::Number`](serde_json::Value::Number), and
+    /// [`NAN`](Self::NAN) becomes [`Value::Null`](serde_json::Value::Null), like
+    /// with [`serde::option_collapse`].
+    ///
+    /// ```
+    /// use serde_json::{json, Value};
+    /// use serial_num::Serial;
+    ///
+    /// assert_eq!(json!(42), (Serial::default() + 42).to_json_value());
+    /// assert_eq!(Value::Null, Serial::NAN.to_json_value());
+    /// ```
+    #[cfg(feature = "serde_json")]
+    #[inline]
+    #[must_use]
+    pub fn to_json_value(self) -> serde_json::Value {
+        if self.is_nan() {
+            serde_json::Value::Null
+        } else {
+            serde_json::Value::from(self.0)
+        }
+    }
+
+    /// Encodes `self` in exactly two bytes, like `bincode` does with
+    /// `bincode::config::standard().with_fixed_int_encoding()`.
+    ///
+    /// With the default `bincode::config::standard()`, integers use a variable-length
+    /// encoding, so a [`Serial`] takes one to three bytes. Use this if you need
+    /// a guaranteed size, without configuring `bincode`.
+    ///
+    /// Use [`Self::bincode_decode_fixed()`] for the reverse.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let serial = Serial::default() + 300;
+    /// let mut buf = [0; 3];
+    ///
+    /// let cfg = bincode::config::standard().with_fixed_int_encoding();
+    /// let len = bincode::encode_into_slice(serial, &mut buf, cfg).unwrap();
+    /// assert_eq!(&buf[..len], serial.bincode_encode_fixed());
+    ///
+    /// let cfg = bincode::config::standard();
+    /// assert_eq!(3, bincode::encode_into_slice(serial, &mut buf, cfg).unwrap());
+    /// ```
+    #[cfg(feature = "bincode")]
+    #[inline]
+    #[must_use]
+    #[expect(
+        clippy::little_endian_bytes,
+        reason = "bincode encodes fixed-size integers as little-endian"
+    )]
+    pub fn bincode_encode_fixed(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+
+    /// Decodes a serial number from two bytes that were encoded like with
+    /// [`Self::bincode_encode_fixed()`].
+    ///
+    /// Since every `u16` is a valid counter or [`NAN`](Self::NAN), this cannot fail.
+    #[cfg(feature = "bincode")]
+    #[inline]
+    #[expect(
+        clippy::little_endian_bytes,
+        reason = "bincode encodes fixed-size integers as little-endian"
+    )]
+    pub fn bincode_decode_fixed(bytes: &[u8; 2]) -> Self {
+        Self(u16::from_le_bytes(*bytes))
+    }
+
+    /// The name of the type in the `Debug` output, and in schemas.
+    const NAME: &'static str = match SENTINEL {
+        NAN_U16 => "Serial",
+        0 => "ZeroNanSerial",
+        _ => "SentinelSerial",
+    };
+
+    /// The counter of the [`Serial`] at the same position on the ring,
+    /// where [`NAN`](Self::NAN) is `u16::MAX`.
+    const fn logical(self) -> u16 {
+        self.0.wrapping_sub(SENTINEL.wrapping_add(1))
+    }
+
+    /// The reverse of [`Self::logical()`].
+    const fn from_logical(n: u16) -> Self {
+        Self(n.wrapping_add(SENTINEL.wrapping_add(1)))
+    }
+}
+
+impl<const SENTINEL: u16> Default for SentinelSerial<SENTINEL> {
+    /// The initial serial number [`ZERO`](Self::ZERO).
+    #[inline]
+    fn default() -> Self {
+        Self::ZERO
+    }
+}
+
+impl<const SENTINEL: u16> fmt::Debug for SentinelSerial<SENTINEL> {
+    /// Prints `Serial(NaN)` for [`NAN`](Self::NAN), and `Serial(n)` with the raw
+    /// value otherwise.
+    ///
+    /// [`ZeroNanSerial`] is printed as `ZeroNanSerial`, and other sentinels
+    /// as `SentinelSerial`.
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.is_nan() {
+            f.write_str(Self::NAME)?;
+            f.write_str("(NaN)")
+        } else {
+            f.debug_tuple(Self::NAME).field(&self.0).finish()
+        }
+    }
+}
+
+/// What happened during [`Serial::increase_report()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "these are all the outcomes of an increase"
+)]
+pub enum IncreaseOutcome {
+    /// The number was increased by one.
+    Advanced,
+    /// The number was the largest counter, and wrapped around to `0`.
+    Wrapped,
+    /// The number was [`NAN`](Serial::NAN), and was left unchanged.
+    WasNan,
+}
+
+/// How close a serial number is to moving out of the comparison window,
+/// as returned by [`Serial::staleness()`].
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_enums,
+    reason = "these are all the levels of the classification"
+)]
+pub enum Staleness {
+    /// Less than half of the window is used.
+    Fresh,
+    /// At least half of the window is used.
+    Approaching,
+    /// At least 90% of the window is used, and comparisons will soon become meaningless.
+    Critical,
+}
+
+/// The error returned by [`Serial::increase_checked_against()`] if the next
+/// serial number is still in use.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct WindowExhausted;
+
+impl fmt::Display for WindowExhausted {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("the next serial number is still in use")
+    }
+}
+
+impl core::error::Error for WindowExhausted {}
+
+/// The error returned by [`Serial::from_ffi()`] if the number is too large.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct FfiRangeError;
+
+impl fmt::Display for FfiRangeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("serial number out of range 0..=65534")
+    }
+}
+
+impl core::error::Error for FfiRangeError {}
+
+/// The error returned by [`Serial::format_into()`] if the buffer is too small.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct BufferTooSmall;
+
+impl fmt::Display for BufferTooSmall {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("buffer is too small to hold the serial number")
+    }
+}
+
+impl core::error::Error for BufferTooSmall {}
+
+/// The error returned when parsing a [`Serial`] from a string fails.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct ParseSerialError;
+
+impl fmt::Display for ParseSerialError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid serial number")
+    }
+}
+
+impl core::error::Error for ParseSerialError {}
+
+/// The error returned when converting a wider integer to a [`Serial`] fails,
+/// because it is out of range.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SerialRangeError;
+
+impl fmt::Display for SerialRangeError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("serial number out of range -1..=65535")
+    }
+}
+
+impl core::error::Error for SerialRangeError {}
+
+impl TryFrom<i32> for Serial {
+    type Error = SerialRangeError;
+
+    /// Converts `0..=65534` to a serial number, and both `65535` and `-1`
+    /// to [`NAN`](Serial::NAN).
+    ///
+    /// Other numbers are out of range. To map any negative number to
+    /// [`NAN`](Serial::NAN), use [`Serial::from_ffi()`].
+    #[inline]
+    fn try_from(n: i32) -> Result<Self, Self::Error> {
+        match n {
+            -1 => Ok(Self::NAN),
+            _ => u16::try_from(n).map(Self).map_err(|_err| SerialRangeError),
+        }
+    }
+}
+
+impl<const SENTINEL: u16> core::str::FromStr for SentinelSerial<SENTINEL> {
+    type Err = ParseSerialError;
+
+    /// Parses a decimal number like `42`, or `NaN` for [`NAN`](Serial::NAN).
+    ///
+    /// `65535` is also parsed as [`NAN`](Serial::NAN).
+    /// For other radixes, use [`Serial::from_str_radix()`].
+    #[inline]
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "NaN" {
+            return Ok(Self::NAN);
+        }
+        Self::from_str_radix(s, 10)
+    }
+}
+
+/// The error returned by [`Serial::unpack_le()`] if the buffer has an odd length.
+#[cfg(feature = "alloc")]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct OddLengthError;
+
+#[cfg(feature = "alloc")]
+impl fmt::Display for OddLengthError {
+    #[inline]
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("buffer of packed serial numbers has an odd length")
+    }
+}
+
+#[cfg(feature = "alloc")]
+impl core::error::Error for OddLengthError {}
+
+impl<const SENTINEL: u16> Add<u16> for SentinelSerial<SENTINEL> {
+    type Output = Self;
+
+    /// Addition with wraparound.
+    ///
+    /// You can add any `u16` to the serial number, but be aware that due to the wraparound
+    /// semantics, adding more than `(u16::MAX-1)/2 = 32767` leads to a result that is
+    /// _less_ than `self`. Adding `u16::MAX` will wraparound to the same value.
+    ///
+    /// If `self.is_nan()`, then the returned serial number is also [`NAN`](Self::NAN).
+    ///
+    /// See also [`Serial::wrapping_add()`].
+    #[inline]
+    fn add(self, rhs: u16) -> Self::Output {
+        self.wrapping_add(rhs)
+    }
+}
+
+/// Total order based on [`Serial::partial_cmp()`], with [`NAN`](Serial::NAN)
+/// being greater than any other number.
+///
+/// ⚠️ This order is **not transitive** across the full number space: for example,
+/// `0 < 20000 < 40000 < 0`. It is only correct if all serial numbers that are compared
+/// with each other (f.e. the keys of a `BTreeMap`) fit into one comparison window.
+/// Otherwise, sorting and ordered collections will silently give meaningless
+/// results. This is why this implementation is only available with the
+/// `unsafe-total-order` feature.
+#[cfg(feature = "unsafe-total-order")]
+impl<const SENTINEL: u16> Ord for SentinelSerial<SENTINEL> {
+    #[inline]
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self.is_nan(), other.is_nan()) {
+            (true, true) => Ordering::Equal,
+            (true, false) => Ordering::Greater,
+            (false, true) => Ordering::Less,
+            (false, false) => Self::partial_cmp(*self, *other).unwrap_or(Ordering::Equal),
+        }
+    }
+}
+
+/// See the [`Ord`] implementation, which is only available with the
+/// `unsafe-total-order` feature.
+#[cfg(feature = "unsafe-total-order")]
+impl<const SENTINEL: u16> PartialOrd for SentinelSerial<SENTINEL> {
+    #[inline]
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl From<Serial> for u32 {
+    /// Promotes the opaque value of a serial number to a wider integer.
+    ///
+    /// This is not a count of increases. The result is in the range `0..=65534`,
+    /// and [`NAN`](Serial::NAN) is represented by `65535` (`u16::MAX`).
+    #[inline]
+    fn from(serial: Serial) -> Self {
+        Self::from(serial.0)
+    }
+}
+
+impl From<Serial> for u64 {
+    /// Promotes the opaque value of a serial number to a wider integer.
+    ///
+    /// This is not a count of increases. The result is in the range `0..=65534`,
+    /// and [`NAN`](Serial::NAN) is represented by `65535` (`u16::MAX`).
+    #[inline]
+    fn from(serial: Serial) -> Self {
+        Self::from(serial.0)
+    }
+}
+
+/// Generates numbers that are biased towards the edges of the number space,
+/// and of the window, where comparisons are most likely to go wrong.
+///
+/// One in four numbers is such an edge case, like `0`, `32767`, `65534`,
+/// or [`NAN`](Serial::NAN). All other numbers are uniformly distributed.
+#[cfg(feature = "arbitrary")]
+impl<'data, const SENTINEL: u16> arbitrary::Arbitrary<'data> for SentinelSerial<SENTINEL> {
+    #[inline]
+    fn arbitrary(u: &mut arbitrary::Unstructured<'data>) -> arbitrary::Result<Self> {
+        if u.ratio::<u8>(1, 4)? {
+            return u.choose(&CANDIDATES).copied().map(Self::from_logical);
+        }
+        <u16 as arbitrary::Arbitrary>::arbitrary(u).map(Self::from_logical)
+    }
+
+    #[inline]
+    fn size_hint(depth: usize) -> (usize, Option<usize>) {
+        arbitrary::size_hint::and(
+            <u8 as arbitrary::Arbitrary>::size_hint(depth),
+            arbitrary::size_hint::or(
+                <u8 as arbitrary::Arbitrary>::size_hint(depth),
+                <u16 as arbitrary::Arbitrary>::size_hint(depth),
+            ),
+        )
+    }
+}
+
+// Implemented by hand, so that the schema has the name of the alias,
+// like `Serial`, instead of `SentinelSerial`.
+#[cfg(feature = "postcard")]
+impl<const SENTINEL: u16> postcard_schema::Schema for SentinelSerial<SENTINEL> {
+    const SCHEMA: &'static postcard_schema::schema::NamedType =
+        &postcard_schema::schema::NamedType {
+            name: Self::NAME,
+            ty: &postcard_schema::schema::DataModelType::NewtypeStruct(u16::SCHEMA),
+        };
+}
+
+// The derives of speedy do not support const generics.
+#[cfg(feature = "speedy")]
+impl<'data, C: speedy::Context, const SENTINEL: u16> speedy::Readable<'data, C>
+    for SentinelSerial<SENTINEL>
+{
+    #[inline]
+    fn read_from<R: speedy::Reader<'data, C>>(reader: &mut R) -> Result<Self, C::Error> {
+        <u16 as speedy::Readable<'data, C>>::read_from(reader).map(Self)
+    }
+
+    #[inline]
+    fn minimum_bytes_needed() -> usize {
+        <u16 as speedy::Readable<'data, C>>::minimum_bytes_needed()
+    }
+}
+
+#[cfg(feature = "speedy")]
+impl<C: speedy::Context, const SENTINEL: u16> speedy::Writable<C> for SentinelSerial<SENTINEL> {
+    #[inline]
+    fn write_to<T: ?Sized + speedy::Writer<C>>(&self, writer: &mut T) -> Result<(), C::Error> {
+        self.0.write_to(writer)
+    }
+
+    #[inline]
+    fn bytes_needed(&self) -> Result<usize, C::Error> {
+        <u16 as speedy::Writable<C>>::bytes_needed(&self.0)
+    }
+}
+
+#[cfg(feature = "valuable")]
+impl valuable::Valuable for Serial {
+    /// Records the number as a `u16`, and [`NAN`](Serial::NAN) as the string `"NaN"`.
+    #[inline]
+    fn as_value(&self) -> valuable::Value<'_> {
+        if self.is_nan() {
+            valuable::Value::String("NaN")
+        } else {
+            valuable::Value::U16(self.0)
+        }
+    }
+
+    #[inline]
+    fn visit(&self, visit: &mut dyn valuable::Visit) {
+        visit.visit_value(self.as_value());
+    }
+}
+
+/// C-compatible representation of a [`Serial`], for use across an FFI boundary.
+///
+/// [`Serial`] itself is `#[repr(transparent)]` over a `u16`, so it already has the
+/// same layout and ABI as a C `uint16_t`, and can be passed as is. This type gives it
+/// a name in generated C headers like those of `cbindgen`:
+///
+/// ```c
+/// typedef struct SerialC {
+///   uint16_t counter;
+/// } SerialC;
+/// ```
+///
+/// The counter is in the range `0..=65534`, and `65535` (`UINT16_MAX`) is the
+/// sentinel for [`NAN`](Serial::NAN), meaning "no serial number".
+///
+/// ```
+/// use serial_num::{Serial, SerialC};
+///
+/// extern "C" fn next(serial: SerialC) -> SerialC {
+///     (Serial::from(serial) + 1).into()
+/// }
+///
+/// let serial = Serial::default();
+/// assert_eq!(serial + 1, Serial::from(next(serial.into())));
+/// assert_eq!(Serial::NAN, Serial::from(next(Serial::NAN.into())));
+/// ```
+#[repr(C)]
+#[derive(Debug, Copy, Clone, Default, PartialEq, Eq, Hash)]
+#[expect(
+    clippy::exhaustive_structs,
+    reason = "the layout is part of the C interface"
+)]
+pub struct SerialC {
+    /// The counter of the serial number, or `65535` for [`NAN`](Serial::NAN).
+    pub counter: u16,
+}
+
+impl From<Serial> for SerialC {
+    #[inline]
+    fn from(serial: Serial) -> Self {
+        Self { counter: serial.0 }
+    }
+}
+
+impl From<SerialC> for Serial {
+    #[inline]
+    fn from(serial: SerialC) -> Self {
+        Self::new_unchecked(serial.counter)
+    }
+}
+
+/// Two-byte serial number with wraparound, that reserves `0` instead of `u16::MAX`
+/// for the special [`NAN`](Self::NAN) value.
+///
+/// This is meant for interoperability with peers that use `0` for "no serial number",
+/// and the range `1..=65535` for actual serial numbers. The default is `1`, and the
+/// point of wraparound is `65535`. This is [`SentinelSerial`] with `0` as the sentinel,
+/// so it has the same methods as [`Serial`], and can be converted to and from it
+/// without losing information.
+///
+/// ```
+/// use serial_num::{Serial, ZeroNanSerial};
+///
+/// assert!(ZeroNanSerial::new_unchecked(0).is_nan());
+/// assert_eq!(ZeroNanSerial::new_unchecked(1), ZeroNanSerial::default());
+///
+/// let num = ZeroNanSerial::new_unchecked(u16::MAX);
+/// assert!(num.precedes(num + 1));
+/// assert_eq!(ZeroNanSerial::default(), num + 1); // wraparound
+///
+/// assert_eq!(Serial::NAN, Serial::from(ZeroNanSerial::NAN));
+/// assert_eq!(Serial::default(), Serial::from(ZeroNanSerial::default()));
+/// ```
+pub type ZeroNanSerial = SentinelSerial<0>;
+
+/// The archived form of a [`ZeroNanSerial`].
+#[cfg(feature = "rkyv")]
+pub type ArchivedZeroNanSerial = ArchivedSentinelSerial<0>;
+
+impl From<ZeroNanSerial> for Serial {
+    /// See [`SentinelSerial::to_serial()`].
+    #[inline]
+    fn from(serial: ZeroNanSerial) -> Self {
+        serial.to_serial()
+    }
+}
+
+impl From<Serial> for ZeroNanSerial {
+    /// See [`SentinelSerial::from_serial()`].
+    #[inline]
+    fn from(serial: Serial) -> Self {
+        Self::from_serial(serial)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<const SENTINEL: u16> ArchivedSentinelSerial<SENTINEL> {
+    #[inline]
+    fn to_native(self) -> SentinelSerial<SENTINEL> {
+        SentinelSerial(self.0.to_native())
+    }
+
+    /// Partial comparison with wraparound against a live [`Serial`].
+    ///
+    /// See [`Serial::partial_cmp()`].
+    #[inline]
+    #[must_use]
+    pub fn partial_cmp(&self, other: SentinelSerial<SENTINEL>) -> Option<Ordering> {
+        self.to_native().partial_cmp(other)
+    }
+
+    /// `True` if `self < other` according to [RFC1982].
+    ///
+    /// See [`Serial::precedes()`].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn precedes(&self, other: SentinelSerial<SENTINEL>) -> bool {
+        self.to_native().precedes(other)
+    }
+
+    /// `True` if `self <= other` according to [RFC1982].
+    ///
+    /// See [`Serial::precedes_or_eq()`].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn precedes_or_eq(&self, other: SentinelSerial<SENTINEL>) -> bool {
+        self.to_native().precedes_or_eq(other)
+    }
+
+    /// `True` if `self > other` according to [RFC1982].
+    ///
+    /// See [`Serial::succeeds()`].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn succeeds(&self, other: SentinelSerial<SENTINEL>) -> bool {
+        self.to_native().succeeds(other)
+    }
+
+    /// `True` if `self >= other` according to [RFC1982].
+    ///
+    /// See [`Serial::succeeds_or_eq()`].
+    ///
+    /// [RFC1982]: https://www.rfc-editor.org/rfc/rfc1982#section-3.2
+    #[inline]
+    #[must_use]
+    pub fn succeeds_or_eq(&self, other: SentinelSerial<SENTINEL>) -> bool {
+        self.to_native().succeeds_or_eq(other)
+    }
+
+    /// Compares with a live [`Serial`], but only if they are no further
+    /// than `window` apart.
+    ///
+    /// See [`Serial::cmp_within()`].
+    #[inline]
+    #[must_use]
+    pub fn cmp_within(&self, other: SentinelSerial<SENTINEL>, window: u16) -> Option<Ordering> {
+        self.to_native().cmp_within(other, window)
+    }
+}
+
+#[cfg(feature = "rkyv")]
+impl<const SENTINEL: u16> From<&ArchivedSentinelSerial<SENTINEL>> for SentinelSerial<SENTINEL> {
+    /// Reads an archived serial number.
+    #[inline]
+    fn from(archived: &ArchivedSentinelSerial<SENTINEL>) -> Self {
+        archived.to_native()
+    }
+}
+
+/// Allocator that recycles released serial numbers.
+///
+/// Fresh serial numbers are handed out in increasing order, starting at
+/// [`Serial::default()`]. Once a serial number is [released](Self::release),
+/// it is reissued by a later call to [`acquire()`](Self::acquire), before any
+/// fresh serial number. Released serial numbers are reissued oldest first,
+/// which keeps the live serial numbers clustered.
+///
+/// If the number of items that are alive at the same time is bounded, this
+/// prevents the problem of items outliving the comparison window, since the
+/// pool never has to hand out more than that many distinct serial numbers.
+///
+/// The pool can keep track of up to `N` released serial numbers.
+///
+/// ```
+/// use serial_num::{Serial, SerialPool};
+///
+/// let mut pool = SerialPool::<8>::new();
+///
+/// let a = pool.acquire();
+/// let b = pool.acquire();
+/// assert!(a.precedes(b));
+///
+/// assert!(pool.release(a));
+/// assert!(!pool.release(a)); // already released
+///
+/// assert_eq!(a, pool.acquire()); // "a" is reissued
+/// assert!(b.precedes(pool.acquire())); // no more released numbers
+/// ```
+#[derive(Debug, Clone)]
+pub struct SerialPool<const N: usize> {
+    next: Serial,
+    released: [Serial; N],
+    head: usize,
+    len: usize,
+}
+
+impl<const N: usize> SerialPool<N> {
+    /// Creates an empty pool.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            next: Serial::ZERO,
+            released: [Serial::NAN; N],
+            head: 0,
+            len: 0,
+        }
+    }
+
+    /// Returns the oldest released serial number, or a fresh one
+    /// if there are none.
+    #[inline]
+    pub fn acquire(&mut self) -> Serial {
+        let Some(serial) = self.released.get_mut(self.head).map(Serial::take) else {
+            return self.next.get_increase();
+        };
+        if serial.is_nan() {
+            return self.next.get_increase();
+        }
+        self.head = self.head.wrapping_add(1).checked_rem(N).unwrap_or(0);
+        self.len = self.len.saturating_sub(1);
+        serial
+    }
+
+    /// Gives a serial number back to the pool, so that it can be reissued.
+    ///
+    /// Returns `false` without recycling the number if it is [`NAN`](Serial::NAN),
+    /// if it was not issued by this pool, if it was already released, or
+    /// if the pool cannot keep track of any more released numbers.
+    #[inline]
+    pub fn release(&mut self, serial: Serial) -> bool {
+        if serial.is_nan() || !serial.precedes(self.next) || self.is_released(serial) {
+            return false;
+        }
+        let Some(tail) = self.head.wrapping_add(self.len).checked_rem(N) else {
+            return false;
+        };
+        match self.released.get_mut(tail) {
+            Some(slot) if slot.is_nan() => {
+                *slot = serial;
+                self.len = self.len.saturating_add(1);
+                true
+            }
+            Some(_) | None => false,
+        }
+    }
+
+    /// Returns the number of released serial numbers that are waiting
+    /// to be reissued.
+    #[inline]
+    #[must_use]
+    pub const fn len_released(&self) -> usize {
+        self.len
+    }
+
+    fn is_released(&self, serial: Serial) -> bool {
+        self.released.contains(&serial)
+    }
+}
+
+impl<const N: usize> Default for SerialPool<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Set of serial numbers within a sliding window, backed by a bitset.
+///
+/// The window starts at a [base](Self::base) number, and covers the `64 * N` numbers
+/// that follow, including the base itself. This is the kind of receive bitmap that
+/// is used to detect duplicates and gaps, like in selective acknowledgements.
+///
+/// The first inserted number becomes the base. Inserting a number ahead of the window
+/// slides the base forward, so that the number fits into it, and forgets the oldest
+/// numbers. Numbers that precede the base are too old to be inserted.
+///
+/// The window should not be larger than the comparison window of `32767` numbers,
+/// so `N` should not exceed `511`.
+///
+/// ```
+/// use serial_num::{Serial, SerialSet};
+///
+/// let start = Serial::default();
+/// let mut set: SerialSet<1> = [start, start + 2].into_iter().collect();
+/// assert!(set.contains(start + 2));
+/// assert!(!set.contains(start + 1)); // gap
+/// assert!(!set.insert(start + 2)); // duplicate
+///
+/// assert!(set.insert(start + 64)); // slides the window
+/// assert_eq!(start + 1, set.base());
+/// assert!(!set.contains(start)); // forgotten
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerialSet<const N: usize> {
+    base: Serial,
+    bits: [u64; N],
+}
+
+impl<const N: usize> SerialSet<N> {
+    /// Creates an empty set, without a base.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            base: Serial::NAN,
+            bits: [0; N],
+        }
+    }
+
+    /// Returns the oldest number in the window, or [`NAN`](Serial::NAN),
+    /// if nothing was inserted yet.
+    #[inline]
+    pub const fn base(&self) -> Serial {
+        self.base
+    }
+
+    /// The number of serial numbers that fit into the window.
+    #[inline]
+    fn capacity() -> u16 {
+        u16::try_from(N.saturating_mul(64)).unwrap_or(u16::MAX)
+    }
+
+    /// Returns `true` if `serial` is in the set.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, serial: Serial) -> bool {
+        self.offset(serial).is_some_and(|offset| self.bit(offset))
+    }
+
+    /// Inserts `serial` into the set, sliding the window forward if necessary.
+    ///
+    /// Returns `true` if the number was not in the set before. Returns `false` if it was,
+    /// if it is [`NAN`](Serial::NAN), or if it precedes the [base](Self::base).
+    #[inline]
+    pub fn insert(&mut self, serial: Serial) -> bool {
+        if serial.is_nan() || N == 0 {
+            return false;
+        }
+        if self.base.is_nan() {
+            self.base = serial;
+        }
+        if serial.precedes(self.base) {
+            return false;
+        }
+        let capacity = Self::capacity();
+        if serial.dist(self.base) >= capacity {
+            self.advance_base(serial.wrapping_sub(capacity.saturating_sub(1)));
+        }
+        let Some(offset) = self.offset(serial) else {
+            return false;
+        };
+        if self.bit(offset) {
+            return false;
+        }
+        if let Some(word) = self.bits.get_mut(usize::from(offset.wrapping_shr(6))) {
+            let mask: u64 = 1;
+            *word |= mask.wrapping_shl(u32::from(offset & 63));
+        }
+        true
+    }
+
+    /// Slides the window forward, so that it starts at `base`.
+    ///
+    /// Numbers that precede the new base are removed from the set.
+    /// Does nothing if `base` precedes the current base, or if it is [`NAN`](Serial::NAN).
+    #[inline]
+    pub fn advance_base(&mut self, base: Serial) {
+        if base.is_nan() || base.precedes_or_eq(self.base) {
+            return;
+        }
+        if self.base.is_nan() {
+            self.base = base;
+            return;
+        }
+        let shift = self.base.dist(base);
+        self.base = base;
+
+        let word_shift = usize::from(shift.wrapping_shr(6));
+        let bit_shift = u32::from(shift & 63);
+        for i in 0..N {
+            let lo = i
+                .checked_add(word_shift)
+                .and_then(|j| self.bits.get(j))
+                .copied()
+                .unwrap_or(0);
+            let hi = i
+                .checked_add(word_shift)
+                .and_then(|j| j.checked_add(1))
+                .and_then(|j| self.bits.get(j))
+                .copied()
+                .unwrap_or(0);
+            let word = if bit_shift == 0 {
+                lo
+            } else {
+                lo.wrapping_shr(bit_shift) | hi.wrapping_shl(u64::BITS.wrapping_sub(bit_shift))
+            };
+            if let Some(dst) = self.bits.get_mut(i) {
+                *dst = word;
+            }
+        }
+    }
+
+    /// Returns the offset of `serial` from the base, if it is within the window.
+    fn offset(&self, serial: Serial) -> Option<u16> {
+        if serial.is_nan() || !serial.succeeds_or_eq(self.base) {
+            return None;
+        }
+        let offset = serial.dist(self.base);
+        (offset < Self::capacity()).then_some(offset)
+    }
+
+    fn bit(&self, offset: u16) -> bool {
+        self.bits
+            .get(usize::from(offset.wrapping_shr(6)))
+            .is_some_and(|word| word.wrapping_shr(u32::from(offset & 63)) & 1 != 0)
+    }
+}
+
+impl<const N: usize> Default for SerialSet<N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> Extend<Serial> for SerialSet<N> {
+    #[inline]
+    fn extend<I: IntoIterator<Item = Serial>>(&mut self, iter: I) {
+        for serial in iter {
+            self.insert(serial);
+        }
+    }
+}
+
+impl<const N: usize> FromIterator<Serial> for SerialSet<N> {
+    #[inline]
+    fn from_iter<I: IntoIterator<Item = Serial>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+/// Map from serial numbers within a sliding window to values, backed by a ring buffer.
+///
+/// This is the value-carrying sibling of [`SerialSet`], for example for a reassembly
+/// buffer. The window starts at a [base](Self::base) number, and covers the `N` numbers
+/// that follow, including the base itself. Lookups are `O(1)`, since every number
+/// in the window has its own slot.
+///
+/// The first inserted number becomes the base. Inserting a number ahead of the window
+/// slides the base forward, so that the number fits into it, and drops the values of
+/// the oldest numbers. Numbers that precede the base are too old to be inserted.
+///
+/// The window should not be larger than the comparison window of `32767` numbers.
+///
+/// ```
+/// use serial_num::{Serial, SerialMap};
+///
+/// let start = Serial::default();
+/// let mut map: SerialMap<&str, 4> = SerialMap::new();
+/// assert_eq!(Ok(None), map.insert(start, "a"));
+/// assert_eq!(Ok(None), map.insert(start + 2, "c"));
+/// assert_eq!(Some(&"c"), map.get(start + 2));
+/// assert_eq!(None, map.get(start + 1)); // gap
+///
+/// assert_eq!(Ok(None), map.insert(start + 4, "e")); // slides the window
+/// assert_eq!(start + 1, map.base());
+/// assert_eq!(None, map.get(start)); // dropped
+/// assert_eq!(Err("z"), map.insert(start, "z")); // too old
+/// ```
+#[derive(Debug, Clone)]
+pub struct SerialMap<V, const N: usize> {
+    base: Serial,
+    head: usize,
+    slots: [Option<V>; N],
+}
+
+impl<V, const N: usize> SerialMap<V, N> {
+    /// Creates an empty map, without a base.
+    #[inline]
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            base: Serial::NAN,
+            head: 0,
+            slots: [const { None }; N],
+        }
+    }
+
+    /// Returns the oldest number in the window, or [`NAN`](Serial::NAN),
+    /// if nothing was inserted yet.
+    #[inline]
+    pub const fn base(&self) -> Serial {
+        self.base
+    }
+
+    /// The number of serial numbers that fit into the window.
+    #[inline]
+    fn capacity() -> u16 {
+        u16::try_from(N).unwrap_or(u16::MAX)
+    }
+
+    /// Returns a reference to the value of `serial`, if it is in the map.
+    #[inline]
+    #[must_use]
+    pub fn get(&self, serial: Serial) -> Option<&V> {
+        let index = self.index(self.offset(serial)?)?;
+        self.slots.get(index)?.as_ref()
+    }
+
+    /// Returns a mutable reference to the value of `serial`, if it is in the map.
+    #[inline]
+    #[must_use]
+    pub fn get_mut(&mut self, serial: Serial) -> Option<&mut V> {
+        let index = self.index(self.offset(serial)?)?;
+        self.slots.get_mut(index)?.as_mut()
+    }
+
+    /// Inserts a value for `serial`, sliding the window forward if necessary.
+    ///
+    /// Returns the previous value of `serial`, if there was one.
+    ///
+    /// # Errors
+    /// Returns `value` back if `serial` is [`NAN`](Serial::NAN),
+    /// if it precedes the [base](Self::base), or if `N` is `0`.
+    #[inline]
+    pub fn insert(&mut self, serial: Serial, value: V) -> Result<Option<V>, V> {
+        if serial.is_nan() || N == 0 {
+            return Err(value);
+        }
+        if self.base.is_nan() {
+            self.base = serial;
+        }
+        if serial.precedes(self.base) {
+            return Err(value);
+        }
+        let capacity = Self::capacity();
+        if serial.dist(self.base) >= capacity {
+            self.advance_base(serial.wrapping_sub(capacity.saturating_sub(1)));
+        }
+        let slot = self
+            .offset(serial)
+            .and_then(|offset| self.index(offset))
+            .and_then(|index| self.slots.get_mut(index));
+        match slot {
+            Some(inner) => Ok(inner.replace(value)),
+            None => Err(value),
+        }
+    }
+
+    /// Removes the value of `serial` from the map, and returns it.
+    #[inline]
+    pub fn remove(&mut self, serial: Serial) -> Option<V> {
+        let index = self.index(self.offset(serial)?)?;
+        self.slots.get_mut(index)?.take()
+    }
+
+    /// Slides the window forward, so that it starts at `base`.
+    ///
+    /// The values of numbers that precede the new base are dropped.
+    /// Does nothing if `base` precedes the current base, or if it is [`NAN`](Serial::NAN).
+    #[inline]
+    pub fn advance_base(&mut self, base: Serial) {
+        if base.is_nan() || base.precedes_or_eq(self.base) {
+            return;
+        }
+        if self.base.is_nan() {
+            self.base = base;
+            return;
+        }
+        let shift = self.base.dist(base);
+        for offset in 0..shift.min(Self::capacity()) {
+            if let Some(slot) = self
+                .index(offset)
+                .and_then(|index| self.slots.get_mut(index))
+            {
+                *slot = None;
+            }
+        }
+        self.head = self.index(shift).unwrap_or(0);
+        self.base = base;
+    }
+
+    /// Returns the offset of `serial` from the base, if it is within the window.
+    fn offset(&self, serial: Serial) -> Option<u16> {
+        if serial.is_nan() || !serial.succeeds_or_eq(self.base) {
+            return None;
+        }
+        let offset = serial.dist(self.base);
+        (offset < Self::capacity()).then_some(offset)
+    }
+
+    /// Returns the index of the slot at `offset` from the base.
+    fn index(&self, offset: u16) -> Option<usize> {
+        self.head.wrapping_add(usize::from(offset)).checked_rem(N)
+    }
+}
+
+impl<V, const N: usize> Default for SerialMap<V, N> {
+    #[inline]
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Iterator adaptor that drops serial numbers which do not succeed every number before them.
+///
+/// This keeps track of the highest number seen so far, and only lets through numbers
+/// that [succeed](Serial::succeeds) it. Duplicates, and numbers that arrive out of order,
+/// are dropped. This can be used to replay a log idempotently.
+///
+/// [`NAN`](Serial::NAN) is always dropped. Numbers that are more than
+/// [`WINDOW`](Serial::WINDOW) ahead of the highest number compare as preceding it,
+/// and are dropped as well.
+///
+/// ```
+/// use serial_num::{DedupSerials, Serial};
+///
+/// let a = Serial::default();
+/// let log = [a, a + 1, a, a + 3, a + 2, a + 3, a + 4];
+/// let deduped: Vec<_> = DedupSerials::new(log).collect();
+/// assert_eq!(vec![a, a + 1, a + 3, a + 4], deduped);
+/// ```
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct DedupSerials<I> {
+    iter: I,
+    highest: Serial,
+}
+
+impl<I: Iterator<Item = Serial>> DedupSerials<I> {
+    /// Wraps an iterator of serial numbers.
+    #[inline]
+    pub fn new<T: IntoIterator<IntoIter = I>>(iter: T) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            highest: Serial::NAN,
+        }
+    }
+
+    /// Wraps an iterator of serial numbers, that only lets through numbers that
+    /// succeed `highest`.
+    ///
+    /// Use this to resume a replay, where `highest` is the last number that was let through.
+    #[inline]
+    pub fn resume<T: IntoIterator<IntoIter = I>>(iter: T, highest: Serial) -> Self {
+        Self {
+            iter: iter.into_iter(),
+            highest,
+        }
+    }
+
+    /// Returns the highest number that was let through, or [`NAN`](Serial::NAN)
+    /// if there was none.
+    #[inline]
+    pub const fn highest(&self) -> Serial {
+        self.highest
+    }
+}
+
+impl<I: Iterator<Item = Serial>> Iterator for DedupSerials<I> {
+    type Item = Serial;
+
+    #[inline]
+    fn next(&mut self) -> Option<Serial> {
+        loop {
+            let serial = self.iter.next()?;
+            if serial.is_nan() {
+                continue;
+            }
+            if self.highest.is_nan() || serial.succeeds(self.highest) {
+                self.highest = serial;
+                return Some(serial);
+            }
+        }
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, self.iter.size_hint().1)
+    }
+}
+
+impl<I: FusedIterator<Item = Serial>> FusedIterator for DedupSerials<I> {}
+
+/// Range of serial numbers from `first` to `last`, including both.
+///
+/// The range must fit into the comparison window, so `first`
+/// [precedes or equals](Serial::precedes_or_eq) `last`. It may straddle the point
+/// of wraparound.
+///
+/// ```
+/// use serial_num::{Serial, SerialRange};
+///
+/// let first = Serial::default() + 65530;
+/// let range = SerialRange::new(first, first + 10).unwrap();
+/// assert!(range.contains(first + 5));
+/// assert!(!range.contains(first + 11));
+/// assert_eq!(10, range.span());
+///
+/// assert_eq!(None, SerialRange::new(first + 10, first)); // reversed
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct SerialRange<const SENTINEL: u16 = { u16::MAX }> {
+    first: SentinelSerial<SENTINEL>,
+    last: SentinelSerial<SENTINEL>,
+}
+
+impl<const SENTINEL: u16> SerialRange<SENTINEL> {
+    /// Creates a range from `first` to `last`, including both.
+    ///
+    /// Returns `None` if `last` precedes `first`, or if one of them is [`NAN`](Serial::NAN).
+    #[inline]
+    #[must_use]
+    pub fn new(first: SentinelSerial<SENTINEL>, last: SentinelSerial<SENTINEL>) -> Option<Self> {
+        SentinelSerial::range_len(first, last)?;
+        Some(Self { first, last })
+    }
+
+    /// Returns the first number in the range.
+    #[inline]
+    pub const fn first(&self) -> SentinelSerial<SENTINEL> {
+        self.first
+    }
+
+    /// Returns the last number in the range.
+    #[inline]
+    pub const fn last(&self) -> SentinelSerial<SENTINEL> {
+        self.last
+    }
+
+    /// Returns the [distance](Serial::dist) from the first to the last number.
+    ///
+    /// Since both ends are included, the range contains `span() + 1` numbers.
+    #[inline]
+    #[must_use]
+    pub fn span(&self) -> u16 {
+        self.first.dist(self.last)
+    }
+
+    /// `True` if `serial` is in the range.
+    #[inline]
+    #[must_use]
+    pub fn contains(&self, serial: SentinelSerial<SENTINEL>) -> bool {
+        serial
+            .window_position(self.first)
+            .is_some_and(|position| position <= self.span())
+    }
+}
+
+/// Endless iterator over the numbers that follow a serial number.
+///
+/// This is returned by [`Serial::successors()`]. It is empty if it was created
+/// from [`NAN`](Serial::NAN), and keeps returning `None` in that case.
+#[derive(Debug, Clone)]
+#[must_use = "iterators are lazy and do nothing unless consumed"]
+pub struct Successors<const SENTINEL: u16 = { u16::MAX }> {
+    next: SentinelSerial<SENTINEL>,
+}
+
+impl<const SENTINEL: u16> Iterator for Successors<SENTINEL> {
+    type Item = SentinelSerial<SENTINEL>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next.is_nan() {
+            return None;
+        }
+        self.next.increase();
+        Some(self.next)
+    }
+
+    #[inline]
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        if self.next.is_nan() {
+            (0, Some(0))
+        } else {
+            (usize::MAX, None)
+        }
+    }
+}
+
+impl<const SENTINEL: u16> FusedIterator for Successors<SENTINEL> {}
+
+/// Checks the invariants of the arithmetic at runtime, for numbers at the edges
+/// of the number space and of the window.
+///
+/// This checks that increased numbers succeed their predecessors, that the distance
+/// is symmetric, and that the sign of the difference agrees with the comparison.
+/// It is meant to be run at startup on targets where the test suite cannot be run.
+///
+/// ```
+/// assert_eq!(Ok(()), serial_num::self_check());
+/// ```
+///
+/// # Errors
+/// Returns a description of the first invariant that does not hold.
+#[cfg(feature = "self-check")]
+#[inline]
+pub fn self_check() -> Result<(), &'static str> {
+    for a in CANDIDATES.map(Serial::new_unchecked) {
+        let mut b = a;
+        b.increase();
+        if a.is_nan() {
+            if !b.is_nan() {
+                return Err("increasing NAN does not result in NAN");
+            }
+            continue;
+        }
+        if !a.precedes(b) || !b.succeeds(a) {
+            return Err("an increased number does not succeed its predecessor");
+        }
+        if a.dist(b) != 1 || b.diff(a) != 1 || a.diff(b) != -1 {
+            return Err("an increased number is not one away from its predecessor");
+        }
+
+        for c in CANDIDATES.map(Serial::new_unchecked) {
+            if a.dist(c) != c.dist(a) {
+                return Err("the distance is not symmetric");
+            }
+            if c.is_nan() {
+                continue;
+            }
+            if a.partial_cmp(c) != Some(a.diff(c).cmp(&0)) {
+                return Err("the sign of the difference does not agree with the comparison");
+            }
+            if a.diff(c) != c.diff(a).saturating_neg() {
+                return Err("the difference is not antisymmetric");
+            }
+        }
+    }
+    Ok(())
+}
+
+/// Comparators for sorting serial numbers with [`sort_unstable_by()`](slice::sort_unstable_by) and similar functions.
+pub mod cmp {
+    use crate::{Serial, NAN_U32};
+    use core::cmp::Ordering;
+
+    /// Returns a comparator that orders serial numbers by how far they are ahead
+    /// of `anchor`, with wraparound.
+    ///
+    /// Unlike [`Serial::partial_cmp()`], this is a total order, which sorting requires.
+    /// It matches [`Serial::partial_cmp()`] if all numbers lie in the window that
+    /// starts at `anchor`, like the [`WINDOW`](Serial::WINDOW) after the oldest number
+    /// that is still in use. Numbers that precede `anchor` are sorted after all others,
+    /// and [`NAN`](Serial::NAN) is sorted last. If `anchor` itself is
+    /// [`NAN`](Serial::NAN), numbers are sorted by their counter.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// let anchor = Serial::default() + 65533;
+    /// let mut packets = [(anchor + 2, 'c'), (Serial::NAN, 'd'), (anchor, 'a'), (anchor + 1, 'b')];
+    ///
+    /// let by_window = serial_num::cmp::by_window(anchor);
+    /// packets.sort_by(|a, b| by_window(&a.0, &b.0));
+    /// assert_eq!(['a', 'b', 'c', 'd'], packets.map(|(_, payload)| payload));
+    /// ```
+    #[inline]
+    pub fn by_window(anchor: Serial) -> impl Fn(&Serial, &Serial) -> Ordering {
+        let start = anchor.or(Serial::ZERO);
+        move |a, b| offset(*a, start).cmp(&offset(*b, start))
+    }
+
+    /// The forward offset of `serial` from `anchor`, or `u32::MAX` for `NAN`.
+    #[expect(
+        clippy::arithmetic_side_effects,
+        reason = "cannot overflow in the arithmetic"
+    )]
+    fn offset(serial: Serial, anchor: Serial) -> u32 {
+        if serial.is_nan() {
+            return u32::MAX;
+        }
+        (u32::from(serial.0) + NAN_U32 - u32::from(anchor.0)) % NAN_U32
+    }
+}
+
+/// Helpers for alternative [serde](https://crates.io/crates/serde) representations
+/// of [`Serial`], to be used with `#[serde(with = "...")]`.
+#[cfg(feature = "serde")]
+pub mod serde {
+    /// Deserialize a [`Serial`](crate::Serial) from either an integer or a string.
+    ///
+    /// Besides integers like `42`, this accepts strings like `"42"`.
+    /// [`NAN`](crate::Serial::NAN) can be represented as `65535`, `"65535"`,
+    /// `"NaN"`, or as a missing value like `null`.
+    ///
+    /// Serialization uses the default integer representation.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Packet {
+    ///     #[serde(with = "serial_num::serde::flexible")]
+    ///     serial: Serial,
+    /// }
+    ///
+    /// let a: Packet = serde_json::from_str(r#"{ "serial": 42 }"#).unwrap();
+    /// let b: Packet = serde_json::from_str(r#"{ "serial": "42" }"#).unwrap();
+    /// assert_eq!(a.serial, b.serial);
+    ///
+    /// let nan: Packet = serde_json::from_str(r#"{ "serial": "NaN" }"#).unwrap();
+    /// assert!(nan.serial.is_nan());
+    /// ```
+    pub mod flexible {
+        use crate::Serial;
+        use core::fmt;
+        use serde::de::{self, Deserializer, Visitor};
+        use serde::{Serialize as _, Serializer};
+
+        /// Serialize a [`Serial`] as an integer.
+        ///
+        /// # Errors
+        /// Returns the serializer's error if serialization fails.
+        #[inline]
+        pub fn serialize<S: Serializer>(serial: &Serial, serializer: S) -> Result<S::Ok, S::Error> {
+            serial.serialize(serializer)
+        }
+
+        /// Deserialize a [`Serial`] from either an integer or a string.
+        ///
+        /// # Errors
+        /// Returns the deserializer's error if the input is neither an integer
+        /// in the range of `u16`, nor a string that the [`FromStr`](core::str::FromStr)
+        /// implementation of [`Serial`] parses, nor `"NaN"`, nor a missing value.
+        #[inline]
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Serial, D::Error> {
+            deserializer.deserialize_any(FlexibleVisitor)
+        }
+
+        struct FlexibleVisitor;
+
+        impl<'de> Visitor<'de> for FlexibleVisitor {
+            type Value = Serial;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a serial number as integer or string, or NaN")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Serial, E> {
+                u16::try_from(v)
+                    .map(Serial::new_unchecked)
+                    .map_err(|_err| E::invalid_value(de::Unexpected::Unsigned(v), &self))
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Serial, E> {
+                u16::try_from(v)
+                    .map(Serial::new_unchecked)
+                    .map_err(|_err| E::invalid_value(de::Unexpected::Signed(v), &self))
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Serial, E> {
+                if v.eq_ignore_ascii_case("nan") {
+                    return Ok(Serial::NAN);
+                }
+                v.parse::<Serial>()
+                    .map_err(|_err| E::invalid_value(de::Unexpected::Str(v), &self))
+            }
+
+            fn visit_none<E: de::Error>(self) -> Result<Serial, E> {
+                Ok(Serial::NAN)
+            }
+
+            fn visit_unit<E: de::Error>(self) -> Result<Serial, E> {
+                Ok(Serial::NAN)
+            }
+
+            fn visit_some<D: Deserializer<'de>>(self, deserializer: D) -> Result<Serial, D::Error> {
+                deserializer.deserialize_any(self)
+            }
+        }
+    }
+
+    /// Serialize a [`Serial`](crate::Serial) with a human-friendly representation
+    /// in human-readable formats.
+    ///
+    /// In formats like JSON, a number is represented as `{"serial": 42}`, and
+    /// [`NAN`](crate::Serial::NAN) is represented as `"NaN"`. In compact formats,
+    /// this uses the default integer representation.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Packet {
+    ///     #[serde(with = "serial_num::serde::labeled")]
+    ///     serial: Serial,
+    /// }
+    ///
+    /// let packet = Packet { serial: Serial::default() + 42 };
+    /// let json = serde_json::to_string(&packet).unwrap();
+    /// assert_eq!(r#"{"serial":{"serial":42}}"#, json);
+    ///
+    /// let packet = Packet { serial: Serial::NAN };
+    /// let json = serde_json::to_string(&packet).unwrap();
+    /// assert_eq!(r#"{"serial":"NaN"}"#, json);
+    /// ```
+    pub mod labeled {
+        use crate::Serial;
+        use core::fmt;
+        use serde::de::{self, value::MapAccessDeserializer, Deserializer, MapAccess, Visitor};
+        use serde::{Deserialize as _, Serialize as _, Serializer};
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        #[serde(rename = "Serial", deny_unknown_fields)]
+        struct Labeled {
+            serial: u16,
+        }
+
+        const NAN_LABEL: &str = "NaN";
+
+        /// Serialize a [`Serial`], using a human-friendly representation
+        /// if the format is human-readable.
+        ///
+        /// # Errors
+        /// Returns the serializer's error if serialization fails.
+        #[inline]
+        pub fn serialize<S: Serializer>(serial: &Serial, serializer: S) -> Result<S::Ok, S::Error> {
+            if !serializer.is_human_readable() {
+                return serial.serialize(serializer);
+            }
+            if serial.is_nan() {
+                serializer.serialize_str(NAN_LABEL)
+            } else {
+                Labeled { serial: serial.0 }.serialize(serializer)
+            }
+        }
+
+        /// Deserialize a [`Serial`], expecting a human-friendly representation
+        /// if the format is human-readable.
+        ///
+        /// # Errors
+        /// Returns the deserializer's error if the input does not match the
+        /// representation that [`serialize()`] produces.
+        #[inline]
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Serial, D::Error> {
+            if deserializer.is_human_readable() {
+                deserializer.deserialize_any(LabeledVisitor)
+            } else {
+                Serial::deserialize(deserializer)
+            }
+        }
+
+        struct LabeledVisitor;
+
+        impl<'de> Visitor<'de> for LabeledVisitor {
+            type Value = Serial;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str(r#"a map like {"serial": 42}, or "NaN""#)
+            }
+
+            fn visit_str<E: de::Error>(self, v: &str) -> Result<Serial, E> {
+                if v == NAN_LABEL {
+                    Ok(Serial::NAN)
+                } else {
+                    Err(E::invalid_value(de::Unexpected::Str(v), &self))
+                }
+            }
+
+            fn visit_map<A: MapAccess<'de>>(self, map: A) -> Result<Serial, A::Error> {
+                let labeled = Labeled::deserialize(MapAccessDeserializer::new(map))?;
+                Ok(Serial::new_unchecked(labeled.serial))
+            }
+        }
+    }
+
+    /// Serialize an [`Option<Serial>`](crate::Serial), treating `Some(NAN)` like `None`.
+    ///
+    /// Both `None` and `Some(NAN)` are serialized as a missing value, like `null`,
+    /// and a missing value is deserialized as `None`. This way, there is only one way
+    /// to say "nothing" on the wire. Deserializing a [`NAN`](crate::Serial::NAN)
+    /// number also results in `None`.
+    ///
+    /// To also accept absent fields, combine this with `#[serde(default)]`.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Packet {
+    ///     #[serde(default, with = "serial_num::serde::option_collapse")]
+    ///     serial: Option<Serial>,
+    /// }
+    ///
+    /// let a = serde_json::to_string(&Packet { serial: None }).unwrap();
+    /// let b = serde_json::to_string(&Packet { serial: Some(Serial::NAN) }).unwrap();
+    /// assert_eq!(r#"{"serial":null}"#, a);
+    /// assert_eq!(a, b);
+    ///
+    /// let packet: Packet = serde_json::from_str("{}").unwrap();
+    /// assert_eq!(None, packet.serial);
+    /// ```
+    pub mod option_collapse {
+        use crate::Serial;
+        use serde::{Deserialize as _, Deserializer, Serializer};
+
+        /// Serialize an [`Option<Serial>`], where `None` and `Some(NAN)` are both
+        /// serialized as a missing value.
+        ///
+        /// # Errors
+        /// Returns the serializer's error if serialization fails.
+        #[inline]
+        pub fn serialize<S: Serializer>(
+            serial: &Option<Serial>,
+            serializer: S,
+        ) -> Result<S::Ok, S::Error> {
+            match *serial {
+                Some(inner) if !inner.is_nan() => serializer.serialize_some(&inner),
+                _ => serializer.serialize_none(),
+            }
+        }
+
+        /// Deserialize an [`Option<Serial>`], where a missing value and
+        /// [`NAN`](Serial::NAN) are both deserialized as `None`.
+        ///
+        /// # Errors
+        /// Returns the deserializer's error if the input is neither a missing value,
+        /// nor a valid [`Serial`].
+        #[inline]
+        pub fn deserialize<'de, D: Deserializer<'de>>(
+            deserializer: D,
+        ) -> Result<Option<Serial>, D::Error> {
+            let serial = Option::<Serial>::deserialize(deserializer)?;
+            Ok(serial.filter(|inner| !inner.is_nan()))
+        }
+    }
+
+    /// Serialize a [`Serial`](crate::Serial) as a newtype struct named `Serial`.
+    ///
+    /// By default, a [`Serial`](crate::Serial) is serialized as a bare integer. Formats that keep
+    /// type names, like [RON](https://crates.io/crates/ron), can use this to
+    /// represent it as `Serial(42)` instead.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// #[derive(serde::Serialize, serde::Deserialize)]
+    /// struct Packet {
+    ///     #[serde(with = "serial_num::serde::named")]
+    ///     serial: Serial,
+    /// }
+    ///
+    /// let packet = Packet { serial: Serial::default() + 42 };
+    /// let json = serde_json::to_string(&packet).unwrap();
+    /// assert_eq!(r#"{"serial":42}"#, json);
+    /// ```
+    pub mod named {
+        use crate::Serial;
+        use serde::{Deserialize as _, Deserializer, Serialize as _, Serializer};
+
+        #[derive(serde::Serialize, serde::Deserialize)]
+        #[serde(rename = "Serial")]
+        struct Named(u16);
+
+        /// Serialize a [`Serial`] as a newtype struct named `Serial`.
+        ///
+        /// # Errors
+        /// Returns the serializer's error if serialization fails.
+        #[inline]
+        pub fn serialize<S: Serializer>(serial: &Serial, serializer: S) -> Result<S::Ok, S::Error> {
+            Named(serial.0).serialize(serializer)
+        }
+
+        /// Deserialize a [`Serial`] from a newtype struct named `Serial`.
+        ///
+        /// # Errors
+        /// Returns the deserializer's error if the input is not a newtype struct
+        /// that contains a `u16`.
+        #[inline]
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Serial, D::Error> {
+            Named::deserialize(deserializer).map(|named| Serial::new_unchecked(named.0))
+        }
+    }
+
+    /// Deserialize a [`Serial`](crate::Serial) from any integer, rejecting numbers
+    /// that do not fit into a `u16` with a descriptive error.
+    ///
+    /// This is useful for data from loosely-typed sources, where integers are wider
+    /// than `u16`. `65535` is deserialized as [`NAN`](crate::Serial::NAN).
+    ///
+    /// Serialization uses the default integer representation.
+    ///
+    /// ```
+    /// use serial_num::Serial;
+    ///
+    /// #[derive(serde::Deserialize)]
+    /// struct Packet {
+    ///     #[serde(with = "serial_num::serde::bounded")]
+    ///     serial: Serial,
+    /// }
+    ///
+    /// let result = serde_json::from_str::<Packet>(r#"{ "serial": 70000 }"#);
+    /// let err = result.map(|packet| packet.serial).unwrap_err();
+    /// assert!(err.to_string().contains("out of range"));
+    ///
+    /// let nan: Packet = serde_json::from_str(r#"{ "serial": 65535 }"#).unwrap();
+    /// assert!(nan.serial.is_nan());
+    /// ```
+    pub mod bounded {
+        use crate::Serial;
+        use core::fmt;
+        use serde::de::{self, Deserializer, Visitor};
+        use serde::{Serialize as _, Serializer};
+
+        /// Serialize a [`Serial`] as an integer.
+        ///
+        /// # Errors
+        /// Returns the serializer's error if serialization fails.
+        #[inline]
+        pub fn serialize<S: Serializer>(serial: &Serial, serializer: S) -> Result<S::Ok, S::Error> {
+            serial.serialize(serializer)
+        }
+
+        /// Deserialize a [`Serial`] from any integer.
+        ///
+        /// Formats that are not self-describing, like `bincode`, read a `u16`,
+        /// which is what [`serialize()`] writes.
+        ///
+        /// # Errors
+        /// Returns the deserializer's error if the input is not an integer,
+        /// or if the integer is negative or greater than `65535`.
+        #[inline]
+        pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Serial, D::Error> {
+            deserializer.deserialize_u16(BoundedVisitor)
+        }
+
+        struct BoundedVisitor;
+
+        impl Visitor<'_> for BoundedVisitor {
+            type Value = Serial;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+                formatter.write_str("a serial number in the range 0..=65535")
+            }
+
+            fn visit_u64<E: de::Error>(self, v: u64) -> Result<Serial, E> {
+                u16::try_from(v).map(Serial::new_unchecked).map_err(|_err| {
+                    E::custom(format_args!(
+                        "serial number {v} is out of range, expected 0..=65535"
+                    ))
+                })
+            }
+
+            fn visit_i64<E: de::Error>(self, v: i64) -> Result<Serial, E> {
+                u16::try_from(v).map(Serial::new_unchecked).map_err(|_err| {
+                    E::custom(format_args!(
+                        "serial number {v} is out of range, expected 0..=65535"
+                    ))
+                })
+            }
+        }
     }
 }
@@ -0,0 +1,40 @@
+use super::*;
+
+/// Chains an increase, an addition, and a difference, which must never overflow,
+/// and must agree with each other.
+#[kani::proof]
+fn increase_add_diff() {
+    let start = Serial::new_unchecked(kani::any());
+    let rhs: u16 = kani::any();
+
+    let mut increased = start;
+    increased.increase();
+    let added = increased + rhs;
+    let diff = added.diff(start);
+    let dist = added.dist(start);
+
+    assert!(dist <= MID_U16);
+    assert_eq!(dist, diff.unsigned_abs());
+
+    if start.is_nan() {
+        assert!(increased.is_nan());
+        assert!(added.is_nan());
+        assert_eq!(0, diff);
+    } else {
+        let expected = (u32::from(start.0) + 1 + u32::from(rhs)) % NAN_U32;
+        assert!(!added.is_nan());
+        assert_eq!(expected, u32::from(added.0));
+        assert_eq!(added.partial_cmp(start), Some(diff.cmp(&0)));
+    }
+}
+
+/// Adding the difference between two numbers to one of them yields the other.
+#[kani::proof]
+fn diff_then_add_signed() {
+    let a = Serial::new_unchecked(kani::any());
+    let b = Serial::new_unchecked(kani::any());
+    kani::assume(!a.is_nan() && !b.is_nan());
+
+    let diff = b.diff(a);
+    assert_eq!(b, a.wrapping_add_signed(diff));
+}
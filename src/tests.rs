@@ -1,18 +1,5 @@
 use super::*;
 
-const CANDIDATES: [u16; 10] = [
-    0,
-    1,
-    2,
-    MID_U16 - 1,
-    MID_U16,
-    MID_U16 + 1,
-    MAX_U16 - 2,
-    MAX_U16 - 1,
-    MAX_U16,
-    NAN_U16,
-];
-
 #[test]
 fn increase_nan() {
     let mut nan = Serial::NAN;
@@ -35,7 +22,7 @@ fn cmp_eq() {
 #[test]
 fn cmp1() {
     let a = Serial::default();
-    let b = Serial(MAX_U16);
+    let b = Serial::new_unchecked(MAX_U16);
     assert!(a.succeeds(b));
     assert!(a.succeeds_or_eq(b));
     assert!(!a.precedes_or_eq(b));
@@ -46,8 +33,8 @@ fn cmp1() {
 
 #[test]
 fn cmp2() {
-    let a = Serial(1000);
-    let b = Serial(u16::MAX - 1000);
+    let a = Serial::new_unchecked(1000);
+    let b = Serial::new_unchecked(u16::MAX - 1000);
     assert!(a.succeeds(b));
     assert!(a.succeeds_or_eq(b));
     assert!(!a.precedes_or_eq(b));
@@ -59,7 +46,7 @@ fn cmp2() {
 #[test]
 fn cmp_edge_case() {
     let zero = Serial::default();
-    let mid = Serial(32767);
+    let mid = Serial::new_unchecked(32767);
 
     assert!(mid.succeeds(zero));
     assert!(mid.succeeds_or_eq(zero));
@@ -72,15 +59,15 @@ fn cmp_edge_case() {
 #[test]
 fn dist1() {
     let a = Serial::default();
-    let b = Serial(MAX_U16);
+    let b = Serial::new_unchecked(MAX_U16);
     assert_eq!(a.dist(b), 1);
     assert_eq!(b.dist(a), 1);
 }
 
 #[test]
 fn dist2() {
-    let a = Serial(1000);
-    let b = Serial(MAX_U16 - 1000);
+    let a = Serial::new_unchecked(1000);
+    let b = Serial::new_unchecked(MAX_U16 - 1000);
     let expected_a_diff_to_zero = 1000;
     let expected_b_diff_to_max = 1000;
     let expected = 1 + expected_a_diff_to_zero + expected_b_diff_to_max;
@@ -91,7 +78,7 @@ fn dist2() {
 #[test]
 fn dist3() {
     let zero = Serial::default();
-    let mid = Serial(32767);
+    let mid = Serial::new_unchecked(32767);
     assert!(zero.precedes(mid));
 
     let actual1 = zero.dist(mid);
@@ -100,7 +87,7 @@ fn dist3() {
     assert_eq!(actual1, 32767);
 
     // if we increase by one, the order flips around, and the distance stays the same
-    let mid_plus_one = Serial(32768);
+    let mid_plus_one = Serial::new_unchecked(32768);
     assert!(zero.succeeds(mid_plus_one));
     assert!(zero.succeeds_or_eq(mid_plus_one));
     assert!(!zero.precedes_or_eq(mid_plus_one));
@@ -113,8 +100,8 @@ fn dist3() {
 
 #[test]
 fn simple_example() {
-    let a = Serial(5_u16);
-    let b = Serial(7_u16);
+    let a = Serial::new_unchecked(5_u16);
+    let b = Serial::new_unchecked(7_u16);
 
     assert!(a.precedes(b));
     assert!(a.precedes_or_eq(b));
@@ -130,8 +117,8 @@ fn simple_example() {
 #[test]
 fn wraparound_example() {
     // serial number 5 comes after sequence number 65000
-    let a = Serial(5_u16);
-    let mut b = Serial(65000_u16);
+    let a = Serial::new_unchecked(5_u16);
+    let mut b = Serial::new_unchecked(65000_u16);
     assert!(a.succeeds(b));
     assert!(a.succeeds_or_eq(b));
     assert!(!a.precedes_or_eq(b));
@@ -166,50 +153,113 @@ fn diff() {
 
 #[test]
 fn plus() {
-    assert_eq!(Serial(5), Serial(3) + 2);
+    assert_eq!(Serial::new_unchecked(5), Serial::new_unchecked(3) + 2);
 
-    assert_eq!(Serial(MAX_U16), Serial(0) + MAX_U16);
-    assert_eq!(Serial(0), Serial(0) + MAX_U16 + 1);
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0) + MAX_U16
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0) + MAX_U16 + 1
+    );
 
-    assert_eq!(Serial(0), Serial(0) + u16::MAX);
-    assert_eq!(Serial(MAX_U16), Serial(MAX_U16) + u16::MAX);
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0) + u16::MAX
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(MAX_U16) + u16::MAX
+    );
 
-    assert_eq!(Serial(5 + MID_U16), Serial(5) + MID_U16);
+    assert_eq!(
+        Serial::new_unchecked(5 + MID_U16),
+        Serial::new_unchecked(5) + MID_U16
+    );
 
-    assert!(Serial(0).precedes(Serial(0) + MID_U16));
-    assert!(Serial(0).succeeds(Serial(1) + MID_U16));
+    assert!(Serial::new_unchecked(0).precedes(Serial::new_unchecked(0) + MID_U16));
+    assert!(Serial::new_unchecked(0).succeeds(Serial::new_unchecked(1) + MID_U16));
 
     assert_eq!(Serial::NAN, Serial::NAN + 1);
 }
 
 #[test]
 fn min() {
-    assert_eq!(Serial(0), Serial(0).min(Serial(5)));
-    assert_eq!(Serial(0), Serial(5).min(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).min(Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(5).min(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(0), Serial(0).min(Serial(MID_U16)));
-    assert_eq!(Serial(0), Serial(MID_U16).min(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).min(Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MID_U16).min(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(MID_U16 + 1), Serial(0).min(Serial(MID_U16 + 1)));
-    assert_eq!(Serial(MID_U16 + 1), Serial(MID_U16 + 1).min(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(MID_U16 + 1),
+        Serial::new_unchecked(0).min(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(
+        Serial::new_unchecked(MID_U16 + 1),
+        Serial::new_unchecked(MID_U16 + 1).min(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(0), Serial(0).min(Serial::NAN));
-    assert_eq!(Serial(0), Serial::NAN.min(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).min(Serial::NAN)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::NAN.min(Serial::new_unchecked(0))
+    );
 }
 
 #[test]
 fn max() {
-    assert_eq!(Serial(5), Serial(0).max(Serial(5)));
-    assert_eq!(Serial(5), Serial(5).max(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(0).max(Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).max(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(MID_U16), Serial(0).max(Serial(MID_U16)));
-    assert_eq!(Serial(MID_U16), Serial(MID_U16).max(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(MID_U16),
+        Serial::new_unchecked(0).max(Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        Serial::new_unchecked(MID_U16),
+        Serial::new_unchecked(MID_U16).max(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(0), Serial(0).max(Serial(MID_U16 + 1)));
-    assert_eq!(Serial(0), Serial(MID_U16 + 1).max(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).max(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MID_U16 + 1).max(Serial::new_unchecked(0))
+    );
 
-    assert_eq!(Serial(0), Serial(0).max(Serial::NAN));
-    assert_eq!(Serial(0), Serial::NAN.max(Serial(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).max(Serial::NAN)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::NAN.max(Serial::new_unchecked(0))
+    );
 }
 
 /// A test with a lot of coverage, but no assertions.
@@ -217,8 +267,8 @@ fn max() {
 fn no_overflows() {
     for n in CANDIDATES {
         for m in CANDIDATES {
-            let a = Serial(n);
-            let b = Serial(m);
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
 
             let _ = a.is_nan();
             let _ = a.dist(b);
@@ -229,7 +279,7 @@ fn no_overflows() {
             let _ = a + MID_U16;
             let _ = a + u16::MAX;
 
-            let mut c = Serial(n);
+            let mut c = Serial::new_unchecked(n);
             for _ in 0..5 {
                 c.increase();
                 let _ = c.increase_get();
@@ -241,27 +291,42 @@ fn no_overflows() {
 
 #[test]
 fn or() {
-    assert_eq!(Serial::NAN.or(Serial(5)), Serial(5));
-    assert_eq!(Serial(1).or(Serial::NAN), Serial(1));
-    assert_eq!(Serial(1).or(Serial(5)), Serial(1));
+    assert_eq!(
+        Serial::NAN.or(Serial::new_unchecked(5)),
+        Serial::new_unchecked(5)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1).or(Serial::NAN),
+        Serial::new_unchecked(1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1).or(Serial::new_unchecked(5)),
+        Serial::new_unchecked(1)
+    );
 }
 
 #[test]
 fn or_default() {
     assert_eq!(Serial::NAN.or_default(), Serial::default());
-    assert_eq!(Serial(1).or_default(), Serial(1));
-    assert_eq!(Serial(5).or_default(), Serial(5));
+    assert_eq!(
+        Serial::new_unchecked(1).or_default(),
+        Serial::new_unchecked(1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(5).or_default(),
+        Serial::new_unchecked(5)
+    );
 }
 
 #[test]
 fn take() {
-    let mut a = Serial(1);
-    let mut b = Serial(2);
+    let mut a = Serial::new_unchecked(1);
+    let mut b = Serial::new_unchecked(2);
 
-    assert_eq!(a.take(), Serial(1));
+    assert_eq!(a.take(), Serial::new_unchecked(1));
     assert_eq!(a, Serial::NAN);
 
-    assert_eq!(b.take(), Serial(2));
+    assert_eq!(b.take(), Serial::new_unchecked(2));
     assert_eq!(b, Serial::NAN);
 }
 
@@ -269,7 +334,7 @@ fn take() {
 #[cfg(feature = "serde")]
 fn serde_json_roundtrip() {
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let encoded = serde_json::to_string(&expected).unwrap();
 
@@ -284,7 +349,7 @@ fn bincode_roundtrip() {
     let cfg = bincode::config::standard().with_fixed_int_encoding();
 
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let mut buf = [0_u8; 2];
         let n_bytes = bincode::encode_into_slice(expected, &mut buf, cfg).unwrap();
@@ -301,7 +366,7 @@ fn borsh_roundtrip() {
     use borsh::BorshDeserialize;
 
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let encoded = borsh::to_vec(&expected).unwrap();
         assert_eq!(2, encoded.len());
@@ -314,7 +379,7 @@ fn borsh_roundtrip() {
 #[test]
 #[cfg(feature = "bytemuck")]
 fn bytemuck_cast_roundtrip() {
-    let original = Serial(42);
+    let original = Serial::new_unchecked(42);
     let casted: u16 = bytemuck::cast(original);
     let casted_back: Serial = bytemuck::cast(casted);
     assert_eq!(original, casted_back);
@@ -323,7 +388,7 @@ fn bytemuck_cast_roundtrip() {
 #[test]
 #[cfg(feature = "bytemuck")]
 fn bytemuck_cast_and_zeroed() {
-    let serial = Serial(42);
+    let serial = Serial::new_unchecked(42);
     let actual_bytes = bytemuck::bytes_of(&serial);
     let expected_bytes = 42_u16.to_le_bytes();
     assert_eq!(&expected_bytes, actual_bytes);
@@ -334,14 +399,22 @@ fn bytemuck_cast_and_zeroed() {
     let expected_u16 = 42_u16;
     assert_eq!(expected_u16, actual_u16);
 
-    let mut actual_zeroed = Serial(42);
+    let mut actual_zeroed = Serial::new_unchecked(42);
     bytemuck::write_zeroes(&mut actual_zeroed);
-    let expected_zeroed = Serial(0);
+    let expected_zeroed = Serial::new_unchecked(0);
     assert_eq!(expected_zeroed, actual_zeroed);
 
-    let mut actual_zeroed = [Serial(0), Serial(1), Serial(2)];
+    let mut actual_zeroed = [
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(2),
+    ];
     bytemuck::fill_zeroes(&mut actual_zeroed);
-    let expected_zeroed = [Serial(0), Serial(0), Serial(0)];
+    let expected_zeroed = [
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0),
+    ];
     assert_eq!(expected_zeroed, actual_zeroed);
 }
 
@@ -358,7 +431,7 @@ fn postcard_maxsize() {
 #[allow(unsafe_code)]
 fn rkyv_roundtrip() {
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&expected).unwrap();
 
@@ -372,7 +445,7 @@ fn rkyv_roundtrip() {
 #[cfg(feature = "rkyv-safe")]
 fn rkyv_safe_roundtrip() {
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&expected).unwrap();
 
@@ -400,7 +473,7 @@ fn speedy_roundtrip() {
     use speedy::{Readable, Writable};
 
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let encoded = expected.write_to_vec().unwrap();
         assert_eq!(2, encoded.len());
@@ -414,7 +487,7 @@ fn speedy_roundtrip() {
 #[cfg(feature = "bitcode")]
 fn bitcode_roundtrip() {
     for n in CANDIDATES {
-        let expected = Serial(n);
+        let expected = Serial::new_unchecked(n);
 
         let encoded = bitcode::encode(&expected);
         assert_eq!(2, encoded.len());
@@ -423,3 +496,3864 @@ fn bitcode_roundtrip() {
         assert_eq!(expected, actual);
     }
 }
+
+#[test]
+#[cfg(feature = "rkyv")]
+#[allow(unsafe_code)]
+fn rkyv_archived_cmp() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
+
+            let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&a).unwrap();
+            let archived = unsafe { rkyv::access_unchecked::<ArchivedSerial>(&bytes[..]) };
+
+            assert_eq!(a.partial_cmp(b), archived.partial_cmp(b));
+            assert_eq!(a.precedes(b), archived.precedes(b));
+            assert_eq!(a.precedes_or_eq(b), archived.precedes_or_eq(b));
+            assert_eq!(a.succeeds(b), archived.succeeds(b));
+            assert_eq!(a.succeeds_or_eq(b), archived.succeeds_or_eq(b));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "rkyv")]
+#[allow(unsafe_code)]
+fn rkyv_archived_cmp_within() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
+
+            let bytes_a = rkyv::to_bytes::<rkyv::rancor::Error>(&a).unwrap();
+            let bytes_b = rkyv::to_bytes::<rkyv::rancor::Error>(&b).unwrap();
+            let archived_a = unsafe { rkyv::access_unchecked::<ArchivedSerial>(&bytes_a[..]) };
+            let archived_b = unsafe { rkyv::access_unchecked::<ArchivedSerial>(&bytes_b[..]) };
+            assert_eq!(b, Serial::from(archived_b));
+
+            let dist = a.dist(b);
+            for window in [
+                0,
+                dist.saturating_sub(1),
+                dist,
+                dist.saturating_add(1),
+                MID_U16,
+            ] {
+                assert_eq!(
+                    a.cmp_within(b, window),
+                    archived_a.cmp_within(archived_b.into(), window)
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn pool_acquire_fresh() {
+    let mut pool = SerialPool::<4>::new();
+    assert_eq!(Serial::new_unchecked(0), pool.acquire());
+    assert_eq!(Serial::new_unchecked(1), pool.acquire());
+    assert_eq!(Serial::new_unchecked(2), pool.acquire());
+}
+
+#[test]
+fn pool_reissue_oldest_released() {
+    let mut pool = SerialPool::<4>::new();
+    let a = pool.acquire();
+    let b = pool.acquire();
+    let c = pool.acquire();
+
+    assert!(pool.release(c));
+    assert!(pool.release(a));
+    assert_eq!(2, pool.len_released());
+
+    assert_eq!(c, pool.acquire());
+    assert_eq!(a, pool.acquire());
+    assert_eq!(0, pool.len_released());
+
+    assert!(b.precedes(pool.acquire()));
+}
+
+#[test]
+fn pool_never_reissue_live() {
+    let mut pool = SerialPool::<4>::new();
+    let a = pool.acquire();
+
+    // double release
+    assert!(pool.release(a));
+    assert!(!pool.release(a));
+    assert_eq!(a, pool.acquire());
+    assert_ne!(a, pool.acquire());
+
+    // never issued by this pool
+    assert!(!pool.release(Serial::new_unchecked(100)));
+    assert!(!pool.release(Serial::NAN));
+    assert_eq!(0, pool.len_released());
+}
+
+#[test]
+fn pool_capacity() {
+    let mut pool = SerialPool::<2>::new();
+    let a = pool.acquire();
+    let b = pool.acquire();
+    let c = pool.acquire();
+
+    assert!(pool.release(a));
+    assert!(pool.release(b));
+    assert!(!pool.release(c));
+
+    // the ring buffer wraps around
+    assert_eq!(a, pool.acquire());
+    assert!(pool.release(c));
+    assert_eq!(b, pool.acquire());
+    assert_eq!(c, pool.acquire());
+    assert_eq!(Serial::new_unchecked(3), pool.acquire());
+
+    let mut pool = SerialPool::<0>::new();
+    let a = pool.acquire();
+    assert!(!pool.release(a));
+    assert_eq!(Serial::new_unchecked(1), pool.acquire());
+}
+
+#[test]
+fn increase_checked_against() {
+    let mut a = Serial::new_unchecked(5);
+    assert_eq!(
+        Ok(Serial::new_unchecked(6)),
+        a.increase_checked_against(Serial::new_unchecked(0))
+    );
+    assert_eq!(Serial::new_unchecked(6), a);
+
+    // the successor is the oldest live number
+    assert_eq!(
+        Err(WindowExhausted),
+        a.increase_checked_against(Serial::new_unchecked(7))
+    );
+    assert_eq!(Serial::new_unchecked(6), a);
+
+    // the number itself may be the oldest live number
+    assert_eq!(
+        Ok(Serial::new_unchecked(7)),
+        a.increase_checked_against(Serial::new_unchecked(6))
+    );
+
+    // exhaustion at the point of wraparound
+    let mut a = Serial::new_unchecked(MAX_U16);
+    assert_eq!(
+        Err(WindowExhausted),
+        a.increase_checked_against(Serial::new_unchecked(0))
+    );
+    assert_eq!(Serial::new_unchecked(MAX_U16), a);
+    assert_eq!(
+        Ok(Serial::new_unchecked(0)),
+        a.increase_checked_against(Serial::new_unchecked(1))
+    );
+
+    // no live numbers
+    let mut a = Serial::new_unchecked(MAX_U16);
+    assert_eq!(
+        Ok(Serial::new_unchecked(0)),
+        a.increase_checked_against(Serial::NAN)
+    );
+
+    // NAN cannot be increased, and never collides
+    let mut nan = Serial::NAN;
+    assert_eq!(Ok(Serial::NAN), nan.increase_checked_against(Serial::NAN));
+    assert_eq!(
+        Ok(Serial::NAN),
+        nan.increase_checked_against(Serial::new_unchecked(0))
+    );
+}
+
+#[test]
+fn debug() {
+    assert_eq!("Serial(NaN)", std::format!("{:?}", Serial::NAN));
+    assert_eq!("Serial(0)", std::format!("{:?}", Serial::new_unchecked(0)));
+    assert_eq!(
+        "Serial(42)",
+        std::format!("{:?}", Serial::new_unchecked(42))
+    );
+    assert_eq!(
+        "Serial(65534)",
+        std::format!("{:?}", Serial::new_unchecked(MAX_U16))
+    );
+}
+
+#[test]
+fn wrapping_add() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            assert_eq!(
+                Serial::new_unchecked(n) + m,
+                Serial::new_unchecked(n).wrapping_add(m)
+            );
+        }
+    }
+}
+
+#[test]
+fn wrapping_sub() {
+    assert_eq!(
+        Serial::new_unchecked(3),
+        Serial::new_unchecked(5).wrapping_sub(2)
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0).wrapping_sub(1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).wrapping_sub(MAX_U16 + 1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(0).wrapping_sub(MAX_U16)
+    );
+
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0).wrapping_sub(u16::MAX)
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(MAX_U16).wrapping_sub(u16::MAX)
+    );
+
+    assert_eq!(Serial::NAN, Serial::NAN.wrapping_sub(1));
+
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            assert_eq!(a, a.wrapping_add(m).wrapping_sub(m));
+            assert_eq!(a, a.wrapping_sub(m).wrapping_add(m));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_flexible() {
+    #[derive(::serde::Deserialize, ::serde::Serialize)]
+    struct Packet {
+        #[serde(with = "crate::serde::flexible")]
+        serial: Serial,
+    }
+
+    let from = |json: &str| serde_json::from_str::<Packet>(json).map(|p| p.serial);
+
+    assert_eq!(
+        Serial::new_unchecked(42),
+        from(r#"{ "serial": 42 }"#).unwrap()
+    );
+    assert_eq!(
+        Serial::new_unchecked(42),
+        from(r#"{ "serial": "42" }"#).unwrap()
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        from(r#"{ "serial": "0" }"#).unwrap()
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        from(r#"{ "serial": 65534 }"#).unwrap()
+    );
+
+    assert_eq!(Serial::NAN, from(r#"{ "serial": 65535 }"#).unwrap());
+    assert_eq!(Serial::NAN, from(r#"{ "serial": "65535" }"#).unwrap());
+    assert_eq!(Serial::NAN, from(r#"{ "serial": "NaN" }"#).unwrap());
+    assert_eq!(Serial::NAN, from(r#"{ "serial": null }"#).unwrap());
+
+    assert!(from(r#"{ "serial": 65536 }"#).is_err());
+    assert!(from(r#"{ "serial": -1 }"#).is_err());
+    assert!(from(r#"{ "serial": "abc" }"#).is_err());
+    assert!(from(r#"{ "serial": 1.5 }"#).is_err());
+
+    // strings are parsed like `FromStr`, without a sign
+    assert!(from(r#"{ "serial": "+42" }"#).is_err());
+    assert!(from(r#"{ "serial": "-0" }"#).is_err());
+    assert!(from(r#"{ "serial": " 42" }"#).is_err());
+    assert!(from(r#"{ "serial": "65536" }"#).is_err());
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        let encoded = serde_json::to_string(&Packet { serial: expected }).unwrap();
+        assert_eq!(expected, from(&encoded).unwrap());
+    }
+}
+
+#[test]
+fn shard() {
+    use core::num::NonZeroU16;
+
+    let one = NonZeroU16::new(1).unwrap();
+    let five = NonZeroU16::new(5).unwrap();
+
+    assert_eq!(None, Serial::NAN.shard(one));
+    assert_eq!(None, Serial::NAN.shard(five));
+    assert_eq!(Some(0), Serial::new_unchecked(MAX_U16).shard(one));
+    assert_eq!(Some(2), Serial::new_unchecked(7).shard(five));
+
+    for n in 1..=7 {
+        let n = NonZeroU16::new(n).unwrap();
+        let mut counts = [0_u32; 7];
+        for i in 0..=MAX_U16 {
+            let shard = Serial::new_unchecked(i).shard(n).unwrap();
+            counts[usize::from(shard)] += 1;
+        }
+        let counts = &counts[..usize::from(n.get())];
+        let min = counts.iter().min().unwrap();
+        let max = counts.iter().max().unwrap();
+        assert!(max - min <= 1);
+    }
+}
+
+#[test]
+fn sequence_gap() {
+    assert_eq!(
+        Some(0),
+        Serial::sequence_gap(Serial::new_unchecked(5), Serial::new_unchecked(6))
+    );
+    assert_eq!(
+        Some(1),
+        Serial::sequence_gap(Serial::new_unchecked(5), Serial::new_unchecked(7))
+    );
+
+    // across the point of wraparound
+    assert_eq!(
+        Some(0),
+        Serial::sequence_gap(Serial::new_unchecked(MAX_U16), Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(3),
+        Serial::sequence_gap(Serial::new_unchecked(MAX_U16 - 1), Serial::new_unchecked(2))
+    );
+
+    // at the edge of the window
+    assert_eq!(
+        Some(MID_U16 - 1),
+        Serial::sequence_gap(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16 + 1))
+    );
+
+    // out-of-order and duplicate arrivals
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::new_unchecked(7), Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::new_unchecked(0), Serial::new_unchecked(MAX_U16))
+    );
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::new_unchecked(5), Serial::new_unchecked(5))
+    );
+
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::NAN, Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        None,
+        Serial::sequence_gap(Serial::new_unchecked(5), Serial::NAN)
+    );
+    assert_eq!(None, Serial::sequence_gap(Serial::NAN, Serial::NAN));
+}
+
+#[test]
+fn into_wider_int() {
+    assert_eq!(65535_u32, u32::from(Serial::NAN));
+    assert_eq!(65535_u64, u64::from(Serial::NAN));
+    assert_eq!(0_u32, u32::from(Serial::new_unchecked(0)));
+    assert_eq!(65534_u64, u64::from(Serial::new_unchecked(MAX_U16)));
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+
+        let wide = u32::from(expected);
+        assert_eq!(
+            expected,
+            Serial::new_unchecked(u16::try_from(wide).unwrap())
+        );
+
+        let wide = u64::from(expected);
+        assert_eq!(
+            expected,
+            Serial::new_unchecked(u16::try_from(wide).unwrap())
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "databuf")]
+fn databuf_roundtrip() {
+    use databuf::config::num::LE;
+    use databuf::{Decode, Encode};
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+
+        let encoded = expected.to_bytes::<LE>();
+        assert_eq!(2, encoded.len());
+
+        let actual = Serial::from_bytes::<LE>(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn closest_to() {
+    let closest = |reference: u16, candidates: &[u16]| {
+        Serial::closest_to(
+            Serial::new_unchecked(reference),
+            candidates.iter().copied().map(Serial::new_unchecked),
+        )
+    };
+
+    assert_eq!(None, closest(0, &[]));
+    assert_eq!(None, closest(0, &[NAN_U16]));
+    assert_eq!(None, closest(NAN_U16, &[0, 1]));
+
+    assert_eq!(Some(Serial::new_unchecked(5)), closest(0, &[NAN_U16, 5]));
+    assert_eq!(Some(Serial::new_unchecked(5)), closest(4, &[5, 10, 1000]));
+
+    // the wraparound distance is used, not numeric closeness
+    assert_eq!(
+        Some(Serial::new_unchecked(MAX_U16)),
+        closest(0, &[2, MAX_U16])
+    );
+    assert_eq!(
+        Some(Serial::new_unchecked(1)),
+        closest(MAX_U16, &[MAX_U16 - 3, 1])
+    );
+    assert_eq!(
+        Some(Serial::new_unchecked(100)),
+        closest(60000, &[30000, 100])
+    );
+
+    // ties resolve towards the predecessor
+    assert_eq!(Some(Serial::new_unchecked(3)), closest(5, &[7, 3]));
+    assert_eq!(Some(Serial::new_unchecked(3)), closest(5, &[3, 7]));
+    assert_eq!(
+        Some(Serial::new_unchecked(MAX_U16)),
+        closest(0, &[1, MAX_U16])
+    );
+    assert_eq!(
+        Some(Serial::new_unchecked(MAX_U16)),
+        closest(0, &[MAX_U16, 1])
+    );
+}
+
+#[test]
+fn new_unchecked() {
+    const NUM: Serial = Serial::new_unchecked(5);
+    const NAN: Serial = Serial::new_unchecked(NAN_U16);
+
+    assert_eq!(Serial::new_unchecked(5), NUM);
+    assert_eq!(Serial::NAN, NAN);
+
+    for n in CANDIDATES {
+        assert_eq!(Serial::new_unchecked(n), Serial::new_unchecked(n));
+    }
+}
+
+#[test]
+fn is_initial() {
+    assert!(Serial::default().is_initial());
+    assert!(Serial::new_unchecked(0).is_initial());
+    assert!(!(Serial::default() + 1).is_initial());
+    assert!(!Serial::new_unchecked(MAX_U16).is_initial());
+    assert!(!Serial::NAN.is_initial());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn pack_le_roundtrip() {
+    let expected = CANDIDATES.map(Serial::new_unchecked);
+
+    let packed = Serial::pack_le(&expected);
+    assert_eq!(2 * CANDIDATES.len(), packed.len());
+    assert_eq!([0xFF, 0xFF], packed[packed.len() - 2..]);
+    assert_eq!([0x01, 0x00], packed[2..4]);
+
+    let actual = Serial::unpack_le(&packed).unwrap();
+    assert_eq!(&expected[..], &actual[..]);
+
+    assert!(Serial::pack_le(&[]).is_empty());
+    assert!(Serial::unpack_le(&[]).unwrap().is_empty());
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn unpack_le_odd_length() {
+    assert_eq!(Err(OddLengthError), Serial::unpack_le(&[0x01]));
+    assert_eq!(Err(OddLengthError), Serial::unpack_le(&[0x01, 0x00, 0x02]));
+}
+
+#[test]
+fn offset_from() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
+            assert_eq!(a.diff(b), a.offset_from(b));
+        }
+    }
+
+    assert_eq!(
+        1,
+        Serial::new_unchecked(0).offset_from(Serial::new_unchecked(MAX_U16))
+    );
+    assert_eq!(
+        -1,
+        Serial::new_unchecked(MAX_U16).offset_from(Serial::new_unchecked(0))
+    );
+}
+
+#[test]
+fn zero_nan_conversion() {
+    assert_eq!(ZeroNanSerial::new_unchecked(0), ZeroNanSerial::NAN);
+    assert_eq!(ZeroNanSerial::new_unchecked(1), ZeroNanSerial::default());
+    assert_eq!(Serial::NAN, Serial::from(ZeroNanSerial::NAN));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::from(ZeroNanSerial::new_unchecked(1))
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::from(ZeroNanSerial::new_unchecked(u16::MAX))
+    );
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        assert_eq!(expected, Serial::from(ZeroNanSerial::from(expected)));
+    }
+}
+
+#[test]
+fn zero_nan_increase() {
+    let mut nan = ZeroNanSerial::NAN;
+    nan.increase();
+    assert_eq!(nan, ZeroNanSerial::NAN);
+    assert_eq!(ZeroNanSerial::NAN, ZeroNanSerial::NAN + 1);
+
+    let mut max = ZeroNanSerial::new_unchecked(u16::MAX);
+    assert_eq!(ZeroNanSerial::new_unchecked(1), max.increase_get()); // wraparound
+    assert_eq!(ZeroNanSerial::new_unchecked(1), max.get_increase());
+    assert_eq!(ZeroNanSerial::new_unchecked(2), max);
+}
+
+#[test]
+fn zero_nan_cmp() {
+    let a = ZeroNanSerial::default();
+    let b = ZeroNanSerial::new_unchecked(u16::MAX);
+    assert!(a.succeeds(b));
+    assert!(a.succeeds_or_eq(b));
+    assert!(!a.precedes_or_eq(b));
+    assert!(b.precedes(a));
+    assert!(b.precedes_or_eq(a));
+    assert!(!b.succeeds_or_eq(a));
+    assert_eq!(1, a.dist(b));
+    assert_eq!(1, a.diff(b));
+    assert_eq!(-1, b.diff(a));
+
+    // the comparison flips at the edge of the window
+    let zero = ZeroNanSerial::default();
+    assert!(zero.precedes(zero + MID_U16));
+    assert!(zero.succeeds(zero + MID_U16 + 1));
+    assert_eq!(MID_U16, zero.dist(zero + MID_U16));
+    assert_eq!(MID_U16, zero.dist(zero + MID_U16 + 1));
+
+    assert_eq!(None, ZeroNanSerial::NAN.partial_cmp(zero));
+    assert_eq!(0, ZeroNanSerial::NAN.dist(ZeroNanSerial::NAN));
+    assert_eq!(MID_U16, ZeroNanSerial::NAN.dist(zero));
+
+    assert_eq!(zero, zero.min(ZeroNanSerial::NAN));
+    assert_eq!(zero, ZeroNanSerial::NAN.max(zero));
+    assert_eq!(b, a.min(b));
+    assert_eq!(a, a.max(b));
+}
+
+#[test]
+fn zero_nan_or_take() {
+    assert_eq!(
+        ZeroNanSerial::new_unchecked(5),
+        ZeroNanSerial::NAN.or(ZeroNanSerial::new_unchecked(5))
+    );
+    assert_eq!(
+        ZeroNanSerial::new_unchecked(1),
+        ZeroNanSerial::new_unchecked(1).or(ZeroNanSerial::NAN)
+    );
+    assert_eq!(ZeroNanSerial::default(), ZeroNanSerial::NAN.or_default());
+
+    let mut a = ZeroNanSerial::new_unchecked(5);
+    assert_eq!(ZeroNanSerial::new_unchecked(5), a.take());
+    assert_eq!(ZeroNanSerial::NAN, a);
+}
+
+#[test]
+fn zero_nan_debug() {
+    assert_eq!(
+        "ZeroNanSerial(NaN)",
+        std::format!("{:?}", ZeroNanSerial::NAN)
+    );
+    assert_eq!(
+        "ZeroNanSerial(1)",
+        std::format!("{:?}", ZeroNanSerial::new_unchecked(1))
+    );
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn zero_nan_serde_roundtrip() {
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let encoded = serde_json::to_string(&expected).unwrap();
+        assert_eq!(std::format!("{n}"), encoded);
+
+        let actual: ZeroNanSerial = serde_json::from_str(&encoded).unwrap();
+        assert_eq!(expected, actual);
+
+        let encoded = ron::to_string(&expected).unwrap();
+        let actual: ZeroNanSerial = ron::from_str(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+    assert_eq!("0", serde_json::to_string(&ZeroNanSerial::NAN).unwrap());
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn zero_nan_bincode_roundtrip() {
+    let cfg = bincode::config::standard().with_fixed_int_encoding();
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let mut buf = [0_u8; 2];
+        let n_bytes = bincode::encode_into_slice(expected, &mut buf, cfg).unwrap();
+        assert_eq!(2, n_bytes);
+        assert_eq!(n.to_le_bytes(), buf);
+
+        let (actual, _): (ZeroNanSerial, _) = bincode::decode_from_slice(&buf, cfg).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+#[cfg(feature = "borsh")]
+fn zero_nan_borsh_roundtrip() {
+    use borsh::BorshDeserialize;
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let encoded = borsh::to_vec(&expected).unwrap();
+        assert_eq!(n.to_le_bytes()[..], encoded[..]);
+
+        let actual = ZeroNanSerial::try_from_slice(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn zero_nan_bytemuck() {
+    use bytemuck::checked::CheckedCastError;
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+        let casted: u16 = bytemuck::cast(expected);
+        assert_eq!(n, casted);
+        assert_eq!(expected, bytemuck::cast::<u16, ZeroNanSerial>(casted));
+    }
+
+    let mut zeroed = ZeroNanSerial::new_unchecked(42);
+    bytemuck::write_zeroes(&mut zeroed);
+    assert!(zeroed.is_nan());
+
+    let bytes = bytemuck::bytes_of(&NAN_U16);
+    assert_eq!(
+        Ok(&ZeroNanSerial::new_unchecked(NAN_U16)),
+        ZeroNanSerial::try_from_bytes_non_nan(bytes)
+    );
+    let bytes = bytemuck::bytes_of(&0_u16);
+    assert_eq!(
+        Err(CheckedCastError::InvalidBitPattern),
+        ZeroNanSerial::try_from_bytes_non_nan(bytes)
+    );
+
+    let numbers = [1, MAX_U16, NAN_U16];
+    let bytes = bytemuck::cast_slice::<u16, u8>(&numbers);
+    let serials = ZeroNanSerial::try_cast_slice_non_nan(bytes).unwrap();
+    assert_eq!(numbers, ZeroNanSerial::as_u16_slice(serials));
+
+    let numbers = [1, 0, NAN_U16];
+    let bytes = bytemuck::cast_slice::<u16, u8>(&numbers);
+    assert_eq!(
+        Err(CheckedCastError::InvalidBitPattern),
+        ZeroNanSerial::try_cast_slice_non_nan(bytes)
+    );
+}
+
+#[test]
+#[cfg(all(feature = "postcard", feature = "serde"))]
+fn zero_nan_postcard_roundtrip() {
+    use postcard::experimental::max_size::MaxSize;
+    use postcard_schema::Schema;
+
+    assert_eq!(3, ZeroNanSerial::POSTCARD_MAX_SIZE);
+    assert_eq!("ZeroNanSerial", ZeroNanSerial::SCHEMA.name);
+    assert_eq!("Serial", Serial::SCHEMA.name);
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let mut buf = [0_u8; 3];
+        let bytes = postcard::to_slice(&expected, &mut buf).unwrap();
+
+        let mut expected_buf = [0_u8; 3];
+        assert_eq!(postcard::to_slice(&n, &mut expected_buf).unwrap(), bytes);
+        assert_eq!(
+            expected,
+            postcard::from_bytes::<ZeroNanSerial>(bytes).unwrap()
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "rkyv")]
+#[allow(unsafe_code)]
+fn zero_nan_rkyv_roundtrip() {
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&expected).unwrap();
+        assert_eq!(n.to_le_bytes()[..], bytes[..]);
+
+        let archived = unsafe { rkyv::access_unchecked::<ArchivedZeroNanSerial>(&bytes[..]) };
+        assert_eq!(archived, &expected);
+        assert_eq!(expected, ZeroNanSerial::from(archived));
+
+        for m in CANDIDATES {
+            let b = ZeroNanSerial::new_unchecked(m);
+            assert_eq!(expected.partial_cmp(b), archived.partial_cmp(b));
+            assert_eq!(expected.precedes(b), archived.precedes(b));
+            assert_eq!(expected.succeeds(b), archived.succeeds(b));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "rkyv-safe")]
+fn zero_nan_rkyv_safe_roundtrip() {
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let bytes = rkyv::to_bytes::<rkyv::rancor::Error>(&expected).unwrap();
+
+        let archived =
+            rkyv::access::<ArchivedZeroNanSerial, rkyv::rancor::Error>(&bytes[..]).unwrap();
+
+        assert_eq!(archived, &expected);
+    }
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn zero_nan_arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    let raw_data: &[u8] = "get_raw_data_from_fuzzer()".as_bytes();
+
+    // the same input draws the same numbers on the ring
+    let mut serials = Unstructured::new(raw_data);
+    let mut zero_nans = Unstructured::new(raw_data);
+    while !serials.is_empty() {
+        let expected = ZeroNanSerial::from(Serial::arbitrary(&mut serials).unwrap());
+        assert_eq!(expected, ZeroNanSerial::arbitrary(&mut zero_nans).unwrap());
+    }
+    assert_eq!(Serial::size_hint(0), ZeroNanSerial::size_hint(0));
+}
+
+#[test]
+#[cfg(feature = "speedy")]
+fn zero_nan_speedy_roundtrip() {
+    use speedy::{Readable, Writable};
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let encoded = expected.write_to_vec().unwrap();
+        assert_eq!(n.write_to_vec().unwrap(), encoded);
+
+        let actual = ZeroNanSerial::read_from_buffer(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+#[cfg(feature = "bitcode")]
+fn zero_nan_bitcode_roundtrip() {
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let encoded = bitcode::encode(&expected);
+        assert_eq!(bitcode::encode(&n), encoded);
+
+        let actual: ZeroNanSerial = bitcode::decode(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+#[cfg(feature = "databuf")]
+fn zero_nan_databuf_roundtrip() {
+    use databuf::config::num::LE;
+    use databuf::{Decode, Encode};
+
+    for n in CANDIDATES {
+        let expected = ZeroNanSerial::new_unchecked(n);
+
+        let encoded = expected.to_bytes::<LE>();
+        assert_eq!(n.to_bytes::<LE>(), encoded);
+
+        let actual = ZeroNanSerial::from_bytes::<LE>(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn by_at_least() {
+    let a = Serial::new_unchecked(MAX_U16 - 1);
+    let b = Serial::new_unchecked(1);
+
+    // across the point of wraparound, "b" succeeds "a" by 3
+    assert!(b.succeeds_by_at_least(a, 0));
+    assert!(b.succeeds_by_at_least(a, 3));
+    assert!(!b.succeeds_by_at_least(a, 4));
+    assert!(!a.succeeds_by_at_least(b, 0));
+
+    assert!(a.precedes_by_at_least(b, 0));
+    assert!(a.precedes_by_at_least(b, 3));
+    assert!(!a.precedes_by_at_least(b, 4));
+    assert!(!b.precedes_by_at_least(a, 0));
+
+    // equal numbers neither precede nor succeed each other
+    assert!(!a.succeeds_by_at_least(a, 0));
+    assert!(!a.precedes_by_at_least(a, 0));
+
+    for n in CANDIDATES {
+        assert!(!Serial::NAN.succeeds_by_at_least(Serial::new_unchecked(n), 0));
+        assert!(!Serial::NAN.precedes_by_at_least(Serial::new_unchecked(n), 0));
+        assert!(!Serial::new_unchecked(n).succeeds_by_at_least(Serial::NAN, 0));
+        assert!(!Serial::new_unchecked(n).precedes_by_at_least(Serial::NAN, 0));
+    }
+}
+
+#[test]
+fn wrapping_add_signed() {
+    assert_eq!(
+        Serial::new_unchecked(7),
+        Serial::new_unchecked(5).wrapping_add_signed(2)
+    );
+    assert_eq!(
+        Serial::new_unchecked(3),
+        Serial::new_unchecked(5).wrapping_add_signed(-2)
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0).wrapping_add_signed(-1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16).wrapping_add_signed(1)
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).wrapping_add_signed(0)
+    );
+    assert_eq!(
+        Serial::new_unchecked(MID_U16 - 1),
+        Serial::new_unchecked(MAX_U16).wrapping_add_signed(i16::MAX)
+    );
+    assert_eq!(
+        Serial::new_unchecked(MID_U16),
+        Serial::new_unchecked(0).wrapping_add_signed(i16::MIN)
+    );
+
+    assert_eq!(Serial::NAN, Serial::NAN.wrapping_add_signed(1));
+    assert_eq!(Serial::NAN, Serial::NAN.wrapping_add_signed(-1));
+}
+
+#[test]
+fn apply_deltas() {
+    let sequence = [
+        Serial::new_unchecked(MAX_U16 - 5),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(10_000),
+        Serial::new_unchecked(MAX_U16 - 20_000),
+        Serial::new_unchecked(MAX_U16 - 20_000),
+    ];
+
+    let deltas = sequence.windows(2).map(|pair| pair[1].diff(pair[0]));
+    assert_eq!(
+        sequence[sequence.len() - 1],
+        sequence[0].apply_deltas(deltas)
+    );
+
+    for end in 0..sequence.len() {
+        let deltas = sequence[..=end]
+            .windows(2)
+            .map(|pair| pair[1].diff(pair[0]));
+        assert_eq!(sequence[end], sequence[0].apply_deltas(deltas));
+    }
+
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).apply_deltas([])
+    );
+    assert_eq!(Serial::NAN, Serial::NAN.apply_deltas([1, -1]));
+
+    // NAN does not consume the deltas
+    let mut consumed = 0;
+    let deltas = core::iter::repeat(1).take(100).inspect(|_| consumed += 1);
+    assert_eq!(Serial::NAN, Serial::NAN.apply_deltas(deltas));
+    assert_eq!(0, consumed);
+
+    // the same as a fold in an iterator pipeline
+    let deltas = sequence.windows(2).map(|pair| pair[1].diff(pair[0]));
+    assert_eq!(
+        sequence[0].apply_deltas(deltas.clone()),
+        deltas.fold(sequence[0], Serial::wrapping_add_signed)
+    );
+}
+
+#[test]
+fn checked_sub() {
+    assert_eq!(
+        Some(0),
+        Serial::new_unchecked(5).checked_sub(Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        Some(2),
+        Serial::new_unchecked(7).checked_sub(Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        None,
+        Serial::new_unchecked(5).checked_sub(Serial::new_unchecked(7))
+    );
+
+    // across the point of wraparound
+    assert_eq!(
+        Some(1),
+        Serial::new_unchecked(0).checked_sub(Serial::new_unchecked(MAX_U16))
+    );
+    assert_eq!(
+        None,
+        Serial::new_unchecked(MAX_U16).checked_sub(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(4),
+        Serial::new_unchecked(2).checked_sub(Serial::new_unchecked(MAX_U16 - 1))
+    );
+
+    // at the edge of the window
+    assert_eq!(
+        Some(MID_U16),
+        Serial::new_unchecked(MID_U16).checked_sub(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        None,
+        Serial::new_unchecked(MID_U16 + 1).checked_sub(Serial::new_unchecked(0))
+    );
+
+    assert_eq!(None, Serial::NAN.checked_sub(Serial::new_unchecked(5)));
+    assert_eq!(None, Serial::new_unchecked(5).checked_sub(Serial::NAN));
+    assert_eq!(None, Serial::NAN.checked_sub(Serial::NAN));
+}
+
+#[test]
+fn stable_hash() {
+    // regression guard: these must never change
+    assert_eq!(
+        0xE220_A839_7B1D_CDAF,
+        Serial::new_unchecked(0).stable_hash()
+    );
+    assert_eq!(
+        0x910A_2DEC_8902_5CC1,
+        Serial::new_unchecked(1).stable_hash()
+    );
+    assert_eq!(
+        0xBDD7_3226_2FEB_6E95,
+        Serial::new_unchecked(42).stable_hash()
+    );
+    assert_eq!(
+        0x6BBE_72F9_8CE1_6945,
+        Serial::new_unchecked(MAX_U16).stable_hash()
+    );
+    assert_eq!(0, Serial::NAN.stable_hash());
+}
+
+#[test]
+#[cfg(feature = "unsafe-total-order")]
+fn unsafe_total_order() {
+    use std::collections::BTreeSet;
+
+    let window = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(3),
+        Serial::NAN,
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(1000),
+    ];
+    let set: BTreeSet<Serial> = window.into_iter().collect();
+    let ordered: std::vec::Vec<Serial> = set.into_iter().collect();
+    assert_eq!(
+        [
+            Serial::new_unchecked(MAX_U16 - 1),
+            Serial::new_unchecked(MAX_U16),
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(3),
+            Serial::new_unchecked(1000),
+            Serial::NAN,
+        ],
+        &ordered[..]
+    );
+
+    assert!(Serial::new_unchecked(0) < Serial::new_unchecked(MID_U16));
+    assert!(Serial::new_unchecked(0) > Serial::new_unchecked(MID_U16 + 1));
+    assert!(Serial::new_unchecked(0) > Serial::new_unchecked(MAX_U16));
+    assert!(Serial::new_unchecked(MAX_U16) < Serial::NAN);
+    assert_eq!(Ordering::Equal, Serial::NAN.cmp(&Serial::NAN));
+
+    // inherent methods still take precedence
+    assert_eq!(None, Serial::NAN.partial_cmp(Serial::new_unchecked(0)));
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::NAN.min(Serial::new_unchecked(0))
+    );
+}
+
+#[test]
+fn steps_to_wrap() {
+    assert_eq!(Some(MAX_U16), Serial::new_unchecked(0).steps_to_wrap());
+    assert_eq!(Some(MAX_U16 - 1), Serial::new_unchecked(1).steps_to_wrap());
+    assert_eq!(Some(0), Serial::new_unchecked(MAX_U16).steps_to_wrap());
+    assert_eq!(None, Serial::NAN.steps_to_wrap());
+
+    let mut num = Serial::new_unchecked(MAX_U16 - 3);
+    for _ in 0..=num.steps_to_wrap().unwrap() {
+        num.increase();
+    }
+    assert_eq!(Serial::new_unchecked(0), num);
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_labeled() {
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Packet {
+        #[serde(with = "crate::serde::labeled")]
+        serial: Serial,
+    }
+
+    // human-readable
+    let json = serde_json::to_string(&Packet {
+        serial: Serial::new_unchecked(42),
+    })
+    .unwrap();
+    assert_eq!(r#"{"serial":{"serial":42}}"#, json);
+    let json = serde_json::to_string(&Packet {
+        serial: Serial::NAN,
+    })
+    .unwrap();
+    assert_eq!(r#"{"serial":"NaN"}"#, json);
+
+    for n in CANDIDATES {
+        let expected = Packet {
+            serial: Serial::new_unchecked(n),
+        };
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(expected, serde_json::from_str(&json).unwrap());
+    }
+
+    assert!(serde_json::from_str::<Packet>(r#"{"serial":42}"#).is_err());
+    assert!(serde_json::from_str::<Packet>(r#"{"serial":"nan"}"#).is_err());
+    assert!(serde_json::from_str::<Packet>(r#"{"serial":{"other":42}}"#).is_err());
+
+    // compact
+    let mut buf = [0_u8; 3];
+    let mut expected_buf = [0_u8; 3];
+    let bytes = postcard::to_slice(
+        &Packet {
+            serial: Serial::new_unchecked(42),
+        },
+        &mut buf,
+    )
+    .unwrap();
+    let expected_bytes = postcard::to_slice(&42_u16, &mut expected_buf).unwrap();
+    assert_eq!(expected_bytes, bytes);
+
+    for n in CANDIDATES {
+        let expected = Packet {
+            serial: Serial::new_unchecked(n),
+        };
+        let bytes = postcard::to_slice(&expected, &mut buf).unwrap();
+        assert_eq!(expected, postcard::from_bytes(bytes).unwrap());
+    }
+}
+
+#[test]
+fn clamp_to_window() {
+    let anchor = Serial::new_unchecked(10);
+
+    // exactly at the edges of the window
+    let ahead = anchor + Serial::WINDOW;
+    assert_eq!(Serial::WINDOW, ahead.dist(anchor));
+    assert_eq!(ahead, ahead.clamp_to_window(anchor));
+    let behind = anchor.wrapping_sub(Serial::WINDOW);
+    assert_eq!(Serial::WINDOW, behind.dist(anchor));
+    assert_eq!(behind, behind.clamp_to_window(anchor));
+
+    // one step beyond the edge ahead is the edge behind
+    assert_eq!(behind, ahead + 1);
+    assert_eq!(behind, (ahead + 1).clamp_to_window(anchor));
+
+    // no number is further away than the full window
+    for n in CANDIDATES {
+        let serial = Serial::new_unchecked(n);
+        assert_eq!(serial, serial.clamp_to_window(anchor));
+        assert_eq!(
+            serial.clamp_to_window_of(anchor, Serial::WINDOW),
+            serial.clamp_to_window(anchor)
+        );
+    }
+
+    assert_eq!(Serial::NAN, Serial::NAN.clamp_to_window(anchor));
+    assert_eq!(anchor, anchor.clamp_to_window(Serial::NAN));
+}
+
+#[test]
+fn clamp_to_window_of() {
+    let anchor = Serial::new_unchecked(10);
+
+    // within the window
+    assert_eq!(
+        Serial::new_unchecked(15),
+        Serial::new_unchecked(15).clamp_to_window_of(anchor, 5)
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).clamp_to_window_of(anchor, 5)
+    );
+    assert_eq!(anchor, anchor.clamp_to_window_of(anchor, 0));
+
+    // just beyond the window
+    assert_eq!(
+        Serial::new_unchecked(15),
+        Serial::new_unchecked(16).clamp_to_window_of(anchor, 5)
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(4).clamp_to_window_of(anchor, 5)
+    );
+
+    // across the point of wraparound
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16 - 9),
+        Serial::new_unchecked(MAX_U16 - 100).clamp_to_window_of(anchor, 20)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(100).clamp_to_window_of(Serial::new_unchecked(MAX_U16), 2)
+    );
+
+    assert_eq!(Serial::NAN, Serial::NAN.clamp_to_window_of(anchor, 5));
+    assert_eq!(
+        Serial::new_unchecked(100),
+        Serial::new_unchecked(100).clamp_to_window_of(Serial::NAN, 5)
+    );
+}
+
+#[test]
+fn advance() {
+    assert_eq!(
+        Serial::new_unchecked(8),
+        Serial::new_unchecked(5).advance(3_u8)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16).advance(1_u16)
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).advance(u16::MAX)
+    );
+    assert_eq!(Serial::NAN, Serial::NAN.advance(1_u8));
+}
+
+#[test]
+fn range_len() {
+    assert_eq!(
+        Some(0),
+        Serial::range_len(Serial::new_unchecked(5), Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        Some(5),
+        Serial::range_len(Serial::new_unchecked(MAX_U16 - 2), Serial::new_unchecked(2))
+    );
+    assert_eq!(
+        None,
+        Serial::range_len(Serial::new_unchecked(2), Serial::new_unchecked(MAX_U16 - 2))
+    );
+
+    // at the edge of the window
+    assert_eq!(
+        Some(MID_U16),
+        Serial::range_len(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        None,
+        Serial::range_len(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16 + 1))
+    );
+
+    assert_eq!(
+        None,
+        Serial::range_len(Serial::NAN, Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        None,
+        Serial::range_len(Serial::new_unchecked(5), Serial::NAN)
+    );
+}
+
+#[test]
+fn serial_set() {
+    let mut set = SerialSet::<2>::new();
+    assert_eq!(Serial::NAN, set.base());
+    assert!(!set.contains(Serial::new_unchecked(0)));
+    assert!(!set.insert(Serial::NAN));
+
+    let start = Serial::new_unchecked(MAX_U16 - 10);
+    assert!(set.insert(start));
+    assert_eq!(start, set.base());
+    assert!(set.insert(start + 70)); // crosses the word boundary and wraps around
+    assert!(!set.insert(start + 70));
+    assert!(!set.insert(start.wrapping_sub(1))); // too old
+    assert!(set.contains(start));
+    assert!(set.contains(start + 70));
+    assert!(!set.contains(start + 69));
+
+    // slide the window, so that the last number is at the last bit
+    assert!(set.insert(start + 130));
+    assert_eq!(start + 3, set.base());
+    assert!(!set.contains(start));
+    assert!(set.contains(start + 70));
+    assert!(set.contains(start + 130));
+
+    // slide by less than a word, keeping bits across the word boundary
+    set.advance_base(start + 40);
+    assert_eq!(start + 40, set.base());
+    assert!(set.contains(start + 70));
+    assert!(set.contains(start + 130));
+
+    // moving the base backward does nothing
+    set.advance_base(start);
+    assert_eq!(start + 40, set.base());
+
+    // slide past the whole window
+    set.advance_base(start + 1000);
+    assert!(!set.contains(start + 130));
+    assert!(set.insert(start + 1000));
+
+    let set: SerialSet<1> = (0..100).map(|n| Serial::new_unchecked(n * 2)).collect();
+    assert_eq!(Serial::new_unchecked(135), set.base());
+    assert!(set.contains(Serial::new_unchecked(136)));
+    assert!(!set.contains(Serial::new_unchecked(137)));
+    assert!(set.contains(Serial::new_unchecked(198)));
+}
+
+#[test]
+fn dist_exact() {
+    let exact: Option<u32> = Serial::new_unchecked(0).dist_exact(Serial::new_unchecked(MID_U16));
+    assert_eq!(Some(32_767), exact);
+    assert_eq!(
+        Some(MID_U16.into()),
+        Serial::new_unchecked(0).dist_exact(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(
+        Some(1),
+        Serial::new_unchecked(MAX_U16).dist_exact(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(0),
+        Serial::new_unchecked(5).dist_exact(Serial::new_unchecked(5))
+    );
+
+    assert_eq!(None, Serial::new_unchecked(0).dist_exact(Serial::NAN));
+    assert_eq!(None, Serial::NAN.dist_exact(Serial::new_unchecked(0)));
+    assert_eq!(None, Serial::NAN.dist_exact(Serial::NAN));
+
+    // `dist` cannot tell these apart
+    assert_eq!(
+        Serial::new_unchecked(0).dist(Serial::new_unchecked(MID_U16)),
+        Serial::new_unchecked(0).dist(Serial::NAN)
+    );
+
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            let (a, b) = (Serial::new_unchecked(a), Serial::new_unchecked(b));
+            if !a.is_nan() && !b.is_nan() {
+                assert_eq!(Some(a.dist(b).into()), a.dist_exact(b));
+            }
+        }
+    }
+}
+
+#[test]
+fn arcs() {
+    assert_eq!(
+        Some((1, u16::MAX - 1)),
+        Serial::new_unchecked(MAX_U16).arcs(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some((u16::MAX - 1, 1)),
+        Serial::new_unchecked(0).arcs(Serial::new_unchecked(MAX_U16))
+    );
+    assert_eq!(
+        Some((MID_U16, MID_U16 + 1)),
+        Serial::new_unchecked(0).arcs(Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(None, Serial::NAN.arcs(Serial::new_unchecked(0)));
+    assert_eq!(None, Serial::new_unchecked(0).arcs(Serial::NAN));
+
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            let (a, b) = (Serial::new_unchecked(a), Serial::new_unchecked(b));
+            let Some((forward, backward)) = a.arcs(b) else {
+                assert!(a.is_nan() || b.is_nan());
+                continue;
+            };
+            if a == b {
+                assert_eq!((0, 0), (forward, backward));
+                continue;
+            }
+            assert_eq!(NAN_U32, u32::from(forward) + u32::from(backward));
+            assert_eq!(a.dist(b), forward.min(backward));
+            assert_eq!(Some((backward, forward)), b.arcs(a));
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_option_collapse() {
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Packet {
+        #[serde(default, with = "crate::serde::option_collapse")]
+        serial: Option<Serial>,
+    }
+
+    let none = serde_json::to_string(&Packet { serial: None }).unwrap();
+    let nan = serde_json::to_string(&Packet {
+        serial: Some(Serial::NAN),
+    })
+    .unwrap();
+    let real = serde_json::to_string(&Packet {
+        serial: Some(Serial::new_unchecked(42)),
+    })
+    .unwrap();
+    assert_eq!(r#"{"serial":null}"#, none);
+    assert_eq!(none, nan);
+    assert_eq!(r#"{"serial":42}"#, real);
+
+    let from = |json: &str| serde_json::from_str::<Packet>(json).unwrap().serial;
+    assert_eq!(None, from(&none));
+    assert_eq!(Some(Serial::new_unchecked(42)), from(&real));
+    assert_eq!(None, from("{}"));
+    assert_eq!(None, from(r#"{"serial":65535}"#));
+
+    // compact
+    let mut buf = [0_u8; 4];
+    let mut expected_buf = [0_u8; 4];
+    let nan = postcard::to_slice(
+        &Packet {
+            serial: Some(Serial::NAN),
+        },
+        &mut buf,
+    )
+    .unwrap();
+    let none = postcard::to_slice(&Packet { serial: None }, &mut expected_buf).unwrap();
+    assert_eq!(none, nan);
+    assert_eq!(None, postcard::from_bytes::<Packet>(nan).unwrap().serial);
+
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let expected = Packet {
+            serial: Some(Serial::new_unchecked(n)),
+        };
+        let bytes = postcard::to_slice(&expected, &mut buf).unwrap();
+        assert_eq!(expected, postcard::from_bytes(bytes).unwrap());
+    }
+}
+
+#[test]
+fn retreat_to() {
+    let mut serial = Serial::new_unchecked(2);
+    assert!(serial.retreat_to(Serial::new_unchecked(MAX_U16 - 2)));
+    assert_eq!(Serial::new_unchecked(MAX_U16 - 2), serial);
+
+    // at the edge of the window
+    let mut serial = Serial::new_unchecked(MID_U16);
+    assert!(serial.retreat_to(Serial::new_unchecked(0)));
+    assert_eq!(Serial::new_unchecked(0), serial);
+
+    let mut serial = Serial::new_unchecked(MID_U16 + 1);
+    assert!(!serial.retreat_to(Serial::new_unchecked(0)));
+    assert_eq!(Serial::new_unchecked(MID_U16 + 1), serial);
+
+    let mut serial = Serial::new_unchecked(5);
+    assert!(!serial.retreat_to(Serial::new_unchecked(5)));
+    assert!(!serial.retreat_to(Serial::new_unchecked(6)));
+    assert!(!serial.retreat_to(Serial::NAN));
+    assert_eq!(Serial::new_unchecked(5), serial);
+
+    let mut serial = Serial::NAN;
+    assert!(!serial.retreat_to(Serial::new_unchecked(5)));
+    assert!(serial.is_nan());
+}
+
+#[test]
+fn const_eq() {
+    const FIRST_REAL: Serial = Serial::ZERO;
+    const TABLE: [Serial; 2] = [FIRST_REAL, Serial::new_unchecked(1)];
+
+    const {
+        assert!(FIRST_REAL.const_eq(Serial::new_unchecked(0)));
+        assert!(!FIRST_REAL.const_eq(Serial::NAN));
+        assert!(FIRST_REAL.is_initial());
+        assert!(!FIRST_REAL.is_nan());
+        assert!(Serial::NAN.is_nan());
+        assert!(!TABLE[0].const_eq(TABLE[1]));
+    };
+
+    assert_eq!(Serial::default(), Serial::ZERO);
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            assert_eq!(
+                Serial::new_unchecked(a) == Serial::new_unchecked(b),
+                Serial::new_unchecked(a).const_eq(Serial::new_unchecked(b))
+            );
+        }
+    }
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn bytemuck_non_nan() {
+    use bytemuck::checked::CheckedCastError;
+
+    let bytes = bytemuck::bytes_of(&42_u16);
+    assert_eq!(
+        Ok(&Serial::new_unchecked(42)),
+        Serial::try_from_bytes_non_nan(bytes)
+    );
+
+    let bytes = bytemuck::bytes_of(&NAN_U16);
+    assert_eq!(
+        Err(CheckedCastError::InvalidBitPattern),
+        Serial::try_from_bytes_non_nan(bytes)
+    );
+    // the unchecked cast accepts the pattern
+    assert!(bytemuck::from_bytes::<Serial>(bytes).is_nan());
+
+    let bytes = bytemuck::bytes_of(&0_u32);
+    assert!(matches!(
+        Serial::try_from_bytes_non_nan(bytes),
+        Err(CheckedCastError::PodCastError(_))
+    ));
+
+    let numbers = [0, 1, MAX_U16];
+    let bytes = bytemuck::cast_slice::<u16, u8>(&numbers);
+    assert_eq!(
+        Ok(&[
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(1),
+            Serial::new_unchecked(MAX_U16)
+        ][..]),
+        Serial::try_cast_slice_non_nan(bytes)
+    );
+
+    let numbers = [0, NAN_U16, MAX_U16];
+    let bytes = bytemuck::cast_slice::<u16, u8>(&numbers);
+    assert_eq!(
+        Err(CheckedCastError::InvalidBitPattern),
+        Serial::try_cast_slice_non_nan(bytes)
+    );
+}
+
+#[test]
+#[cfg(feature = "valuable")]
+fn valuable_value() {
+    use valuable::{Valuable, Value, Visit};
+
+    #[derive(Default)]
+    struct Record(Option<std::string::String>);
+
+    impl Visit for Record {
+        fn visit_value(&mut self, value: Value<'_>) {
+            self.0 = Some(match value {
+                Value::U16(n) => std::format!("u16:{n}"),
+                Value::String(s) => std::format!("str:{s}"),
+                other => std::format!("{other:?}"),
+            });
+        }
+    }
+
+    let record = |serial: Serial| {
+        let mut record = Record::default();
+        serial.visit(&mut record);
+        record.0.unwrap()
+    };
+    assert_eq!("u16:0", record(Serial::new_unchecked(0)));
+    assert_eq!("u16:65534", record(Serial::new_unchecked(MAX_U16)));
+    assert_eq!("str:NaN", record(Serial::NAN));
+
+    assert!(matches!(
+        Serial::new_unchecked(42).as_value(),
+        Value::U16(42)
+    ));
+    assert!(matches!(Serial::NAN.as_value(), Value::String("NaN")));
+}
+
+#[test]
+fn increase_counting() {
+    let mut serial = Serial::new_unchecked(MAX_U16);
+    assert_eq!(1, serial.increase_counting(1));
+    assert_eq!(Serial::new_unchecked(0), serial);
+
+    let mut serial = Serial::new_unchecked(10);
+    assert_eq!(0, serial.increase_counting(0));
+    assert_eq!(Serial::new_unchecked(10), serial);
+
+    // multiple full cycles
+    let mut serial = Serial::new_unchecked(10);
+    assert_eq!(3, serial.increase_counting(3 * NAN_U32));
+    assert_eq!(Serial::new_unchecked(10), serial);
+
+    let mut serial = Serial::new_unchecked(10);
+    assert_eq!(3, serial.increase_counting(3 * NAN_U32 + 5));
+    assert_eq!(Serial::new_unchecked(15), serial);
+
+    let mut serial = Serial::new_unchecked(MAX_U16);
+    assert_eq!(65_537, serial.increase_counting(u32::MAX));
+    assert_eq!(Serial::new_unchecked(MAX_U16), serial);
+
+    // the same as repeated increases
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let mut expected = Serial::new_unchecked(n);
+        let mut expected_wraps = 0;
+        for _ in 0..70_000 {
+            if expected == Serial::new_unchecked(MAX_U16) {
+                expected_wraps += 1;
+            }
+            expected.increase();
+        }
+        let mut actual = Serial::new_unchecked(n);
+        assert_eq!(expected_wraps, actual.increase_counting(70_000));
+        assert_eq!(expected, actual);
+    }
+
+    let mut serial = Serial::NAN;
+    assert_eq!(0, serial.increase_counting(100_000));
+    assert!(serial.is_nan());
+}
+
+#[test]
+fn antipode() {
+    assert_eq!(
+        Serial::new_unchecked(MID_U16),
+        Serial::new_unchecked(0).antipode()
+    );
+    assert_eq!(
+        Serial::new_unchecked(MID_U16 - 1),
+        Serial::new_unchecked(MAX_U16).antipode()
+    );
+    assert!(Serial::NAN.antipode().is_nan());
+
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let serial = Serial::new_unchecked(n);
+        let antipode = serial.antipode();
+        assert_eq!(MID_U16, serial.dist(antipode));
+        assert!(serial.precedes(antipode));
+        assert!(serial.succeeds(antipode + 1));
+
+        // within one of `serial`, due to the odd ring size
+        assert_eq!(serial.wrapping_sub(1), antipode.antipode());
+        assert_eq!(1, serial.dist(antipode.antipode()));
+    }
+}
+
+#[test]
+fn ffi_roundtrip() {
+    for n in CANDIDATES {
+        let serial = Serial::new_unchecked(n);
+        assert_eq!(Ok(serial), Serial::from_ffi(serial.to_ffi()));
+    }
+    assert_eq!(-1, Serial::NAN.to_ffi());
+    assert_eq!(0, Serial::new_unchecked(0).to_ffi());
+    assert_eq!(65534, Serial::new_unchecked(MAX_U16).to_ffi());
+
+    assert_eq!(Ok(Serial::NAN), Serial::from_ffi(-1));
+    assert_eq!(Ok(Serial::NAN), Serial::from_ffi(i32::MIN));
+    assert_eq!(Ok(Serial::new_unchecked(MAX_U16)), Serial::from_ffi(65534));
+    assert_eq!(Err(FfiRangeError), Serial::from_ffi(65535));
+    assert_eq!(Err(FfiRangeError), Serial::from_ffi(i32::MAX));
+}
+
+#[test]
+fn serial_c_roundtrip() {
+    extern "C" fn echo(serial: SerialC) -> SerialC {
+        serial
+    }
+
+    assert_eq!(core::mem::size_of::<u16>(), core::mem::size_of::<SerialC>());
+    assert_eq!(
+        core::mem::align_of::<u16>(),
+        core::mem::align_of::<SerialC>()
+    );
+
+    for n in CANDIDATES {
+        let serial = Serial::new_unchecked(n);
+        let c = SerialC::from(serial);
+        assert_eq!(n, c.counter);
+        assert_eq!(serial, Serial::from(echo(c)));
+    }
+    assert_eq!(NAN_U16, SerialC::from(Serial::NAN).counter);
+    assert_eq!(Serial::default(), Serial::from(SerialC::default()));
+}
+
+#[test]
+fn comparison_is_meaningful() {
+    assert_eq!(MID_U16, Serial::WINDOW);
+
+    assert!(Serial::new_unchecked(0).comparison_is_meaningful(Serial::new_unchecked(0)));
+    assert!(Serial::new_unchecked(0).comparison_is_meaningful(Serial::new_unchecked(MID_U16 - 1)));
+    assert!(
+        Serial::new_unchecked(MAX_U16).comparison_is_meaningful(Serial::new_unchecked(MID_U16 - 2))
+    );
+
+    // at the flip point, from both sides
+    assert!(!Serial::new_unchecked(0).comparison_is_meaningful(Serial::new_unchecked(MID_U16)));
+    assert!(!Serial::new_unchecked(0).comparison_is_meaningful(Serial::new_unchecked(MID_U16 + 1)));
+    assert!(Serial::new_unchecked(0).comparison_is_meaningful(Serial::new_unchecked(MID_U16 + 2)));
+
+    assert!(!Serial::new_unchecked(0).comparison_is_meaningful(Serial::NAN));
+    assert!(!Serial::NAN.comparison_is_meaningful(Serial::new_unchecked(0)));
+    assert!(!Serial::NAN.comparison_is_meaningful(Serial::NAN));
+
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            let (a, b) = (Serial::new_unchecked(a), Serial::new_unchecked(b));
+            assert_eq!(a.comparison_is_meaningful(b), b.comparison_is_meaningful(a));
+        }
+    }
+}
+
+#[test]
+fn dedup_serials() {
+    let collect = |log: &[u16]| {
+        DedupSerials::new(log.iter().map(|&n| Serial::new_unchecked(n)))
+            .map(|serial| serial.0)
+            .collect::<std::vec::Vec<_>>()
+    };
+
+    assert!(collect(&[]).is_empty());
+    assert_eq!([5, 6, 8], *collect(&[5, 5, 6, 5, 8, 7, 6, 8]));
+
+    // across the wraparound boundary
+    assert_eq!(
+        [MAX_U16 - 1, MAX_U16, 0, 2],
+        *collect(&[MAX_U16 - 1, MAX_U16, MAX_U16 - 1, 0, MAX_U16, 2, 1])
+    );
+
+    // NAN is dropped, also as the first number
+    assert_eq!([1, 2], *collect(&[NAN_U16, 1, NAN_U16, 2]));
+
+    // too far ahead compares as preceding
+    assert_eq!([0, MID_U16], *collect(&[0, MID_U16 + 1, MID_U16]));
+
+    let mut dedup = DedupSerials::resume(
+        [
+            Serial::new_unchecked(1),
+            Serial::new_unchecked(2),
+            Serial::new_unchecked(3),
+        ],
+        Serial::new_unchecked(2),
+    );
+    assert_eq!(Serial::new_unchecked(2), dedup.highest());
+    assert_eq!(Some(Serial::new_unchecked(3)), dedup.next());
+    assert_eq!(Serial::new_unchecked(3), dedup.highest());
+    assert_eq!(None, dedup.next());
+}
+
+#[test]
+fn nan_if() {
+    assert_eq!(Serial::NAN, Serial::new_unchecked(5).nan_if(true));
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).nan_if(false)
+    );
+    assert_eq!(Serial::NAN, Serial::NAN.nan_if(false));
+
+    assert_eq!(
+        Serial::NAN,
+        Serial::new_unchecked(5).nan_if_eq(Serial::new_unchecked(5))
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(5).nan_if_eq(Serial::new_unchecked(6))
+    );
+    assert_eq!(Serial::NAN, Serial::NAN.nan_if_eq(Serial::NAN));
+    assert_eq!(Serial::NAN, Serial::NAN.nan_if_eq(Serial::new_unchecked(5)));
+}
+
+#[test]
+fn half_window_boundary() {
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let a = Serial::new_unchecked(n);
+
+        // `b` is exactly one window ahead: `a < b`
+        let b = a + MID_U16;
+        assert_eq!(Some(Ordering::Less), a.partial_cmp(b));
+        assert_eq!(Some(Ordering::Greater), b.partial_cmp(a));
+        assert_eq!(MID_U16, a.dist(b));
+        assert_eq!(MID_U16, b.dist(a));
+        assert_eq!(-32767, a.diff(b));
+        assert_eq!(32767, b.diff(a));
+
+        // `c` is one more ahead, so the comparison flips: `a` is one window ahead of `c`
+        let c = a + (MID_U16 + 1);
+        assert_eq!(c + MID_U16, a);
+        assert_eq!(Some(Ordering::Greater), a.partial_cmp(c));
+        assert_eq!(Some(Ordering::Less), c.partial_cmp(a));
+        assert_eq!(MID_U16, a.dist(c));
+        assert_eq!(MID_U16, c.dist(a));
+        assert_eq!(32767, a.diff(c));
+        assert_eq!(-32767, c.diff(a));
+    }
+
+    // pinned values around the flip point
+    assert_eq!(
+        -32767,
+        Serial::new_unchecked(0).diff(Serial::new_unchecked(32767))
+    );
+    assert_eq!(
+        32767,
+        Serial::new_unchecked(0).diff(Serial::new_unchecked(32768))
+    );
+    assert_eq!(
+        32766,
+        Serial::new_unchecked(0).diff(Serial::new_unchecked(32769))
+    );
+    assert_eq!(
+        -32766,
+        Serial::new_unchecked(0).diff(Serial::new_unchecked(32766))
+    );
+}
+
+#[test]
+fn diff_into() {
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            let (a, b) = (Serial::new_unchecked(a), Serial::new_unchecked(b));
+            assert_eq!(i32::from(a.diff(b)), a.diff_into(b));
+        }
+    }
+    assert_eq!(
+        -32767,
+        Serial::new_unchecked(0).diff_into(Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        32767,
+        Serial::new_unchecked(0).diff_into(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(0, Serial::NAN.diff_into(Serial::NAN));
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn as_u16_slice() {
+    let mut serials = CANDIDATES.map(Serial::new_unchecked);
+    assert_eq!(CANDIDATES, *Serial::as_u16_slice(&serials));
+
+    let counters = Serial::as_u16_slice_mut(&mut serials);
+    if let Some(first) = counters.first_mut() {
+        *first = NAN_U16;
+    }
+    assert_eq!(Some(&Serial::NAN), serials.first());
+
+    assert!(Serial::as_u16_slice(&[]).is_empty());
+    assert_eq!(
+        [0, 0xFFFF],
+        *Serial::as_u16_slice(&[Serial::default(), Serial::NAN])
+    );
+}
+
+#[test]
+#[cfg(all(feature = "bytemuck", feature = "alloc"))]
+fn into_u16_vec() {
+    let serials = CANDIDATES.map(Serial::new_unchecked).to_vec();
+    assert_eq!(CANDIDATES.to_vec(), Serial::into_u16_vec(serials));
+}
+
+#[test]
+#[cfg(feature = "self-check")]
+fn self_check() {
+    assert_eq!(Ok(()), crate::self_check());
+}
+
+#[test]
+fn from_low_bits() {
+    assert_eq!(Serial::new_unchecked(0), Serial::from_low_bits(0x1_0000));
+    assert_eq!(Serial::new_unchecked(0), Serial::from_low_bits(0));
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::from_low_bits(0xFFFE)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::from_low_bits(u64::MAX - 0xFFFE)
+    );
+    assert_eq!(Serial::NAN, Serial::from_low_bits(0xFFFF));
+    assert_eq!(Serial::NAN, Serial::from_low_bits(u64::MAX));
+
+    for n in CANDIDATES {
+        assert_eq!(
+            Serial::new_unchecked(n),
+            Serial::from_low_bits(u64::from(n) | 0x5_0000)
+        );
+    }
+}
+
+#[test]
+fn cmp_within() {
+    let a = Serial::new_unchecked(MAX_U16 - 5);
+    assert_eq!(
+        Some(Ordering::Less),
+        a.cmp_within(Serial::new_unchecked(4), 10)
+    );
+    assert_eq!(None, a.cmp_within(Serial::new_unchecked(5), 10));
+    assert_eq!(
+        Some(Ordering::Greater),
+        Serial::new_unchecked(4).cmp_within(a, 10)
+    );
+    assert_eq!(None, Serial::new_unchecked(5).cmp_within(a, 10));
+    assert_eq!(Some(Ordering::Equal), a.cmp_within(a, 0));
+    assert_eq!(None, a.cmp_within(a + 1, 0));
+
+    assert_eq!(None, Serial::NAN.cmp_within(Serial::NAN, u16::MAX));
+    assert_eq!(None, a.cmp_within(Serial::NAN, u16::MAX));
+
+    // the full window is the same as `partial_cmp`
+    for a in CANDIDATES {
+        for b in CANDIDATES {
+            let (a, b) = (Serial::new_unchecked(a), Serial::new_unchecked(b));
+            assert_eq!(a.partial_cmp(b), a.cmp_within(b, MID_U16));
+        }
+    }
+}
+
+#[test]
+fn landmarks() {
+    assert_eq!(Serial::new_unchecked(16383), Serial::QUARTER);
+    assert_eq!(Serial::new_unchecked(MID_U16), Serial::HALF);
+    assert_eq!(Serial::ZERO + MID_U16, Serial::HALF);
+    assert_eq!(MID_U16, Serial::ZERO.dist(Serial::HALF));
+    assert_eq!(Serial::HALF, Serial::ZERO.antipode());
+
+    assert!(Serial::ZERO.precedes(Serial::HALF));
+    assert!(Serial::ZERO.succeeds(Serial::HALF + 1));
+    assert!(Serial::QUARTER.precedes(Serial::HALF));
+    assert!(Serial::HALF.precedes(Serial::HALF + 16384));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_ron() {
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Named(#[serde(with = "crate::serde::named")] Serial);
+
+    let named_config = || ron::ser::PrettyConfig::new().struct_names(true);
+
+    // transparent by default
+    assert_eq!("42", ron::to_string(&Serial::new_unchecked(42)).unwrap());
+    assert_eq!(
+        "42",
+        ron::ser::to_string_pretty(&Serial::new_unchecked(42), named_config()).unwrap()
+    );
+    assert_eq!(
+        Serial::new_unchecked(42),
+        ron::from_str::<Serial>("42").unwrap()
+    );
+    assert!(ron::from_str::<Serial>("Serial(42)").is_err());
+
+    // with the type name
+    assert_eq!(
+        "Named(Serial(42))",
+        ron::ser::to_string_pretty(&Named(Serial::new_unchecked(42)), named_config()).unwrap()
+    );
+    assert_eq!(
+        Named(Serial::new_unchecked(42)),
+        ron::from_str("Named(Serial(42))").unwrap()
+    );
+
+    for n in CANDIDATES {
+        let expected = Named(Serial::new_unchecked(n));
+        let encoded = ron::ser::to_string_pretty(&expected, named_config()).unwrap();
+        assert_eq!(expected, ron::from_str(&encoded).unwrap());
+
+        let expected = Serial::new_unchecked(n);
+        let encoded = ron::to_string(&expected).unwrap();
+        assert_eq!(expected, ron::from_str::<Serial>(&encoded).unwrap());
+    }
+
+    // no change for formats that drop type names
+    assert_eq!(
+        "42",
+        serde_json::to_string(&Serial::new_unchecked(42)).unwrap()
+    );
+    assert_eq!(
+        "42",
+        serde_json::to_string(&Named(Serial::new_unchecked(42))).unwrap()
+    );
+}
+
+#[test]
+fn is_next_after() {
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(0).successor()
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16).successor()
+    );
+    assert!(Serial::NAN.successor().is_nan());
+
+    // contiguous, across the wraparound
+    let log = [MAX_U16 - 1, MAX_U16, 0, 1, 2].map(Serial::new_unchecked);
+    assert!(log.windows(2).all(|pair| pair[1].is_next_after(pair[0])));
+
+    // injected gap
+    let log = [MAX_U16 - 1, MAX_U16, 1, 2].map(Serial::new_unchecked);
+    assert!(!log.windows(2).all(|pair| pair[1].is_next_after(pair[0])));
+    assert!(!Serial::new_unchecked(1).is_next_after(Serial::new_unchecked(MAX_U16)));
+
+    assert!(!Serial::new_unchecked(0).is_next_after(Serial::new_unchecked(1)));
+    assert!(!Serial::NAN.is_next_after(Serial::NAN));
+    assert!(!Serial::NAN.is_next_after(Serial::new_unchecked(MAX_U16)));
+    assert!(!Serial::new_unchecked(0).is_next_after(Serial::NAN));
+}
+
+#[test]
+fn search_window() {
+    assert_eq!(Err(0), Serial::search_window(&[], Serial::new_unchecked(5)));
+
+    // straddles the point of wraparound
+    let sorted = [MAX_U16 - 4, MAX_U16 - 2, MAX_U16, 1, 3].map(Serial::new_unchecked);
+    for (i, &serial) in sorted.iter().enumerate() {
+        assert_eq!(Ok(i), Serial::search_window(&sorted, serial));
+    }
+    assert_eq!(
+        Err(1),
+        Serial::search_window(&sorted, Serial::new_unchecked(MAX_U16 - 3))
+    );
+    assert_eq!(
+        Err(3),
+        Serial::search_window(&sorted, Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Err(4),
+        Serial::search_window(&sorted, Serial::new_unchecked(2))
+    );
+    assert_eq!(
+        Err(5),
+        Serial::search_window(&sorted, Serial::new_unchecked(4))
+    );
+    assert_eq!(
+        Err(0),
+        Serial::search_window(&sorted, Serial::new_unchecked(MAX_U16 - 5))
+    );
+    assert_eq!(Err(5), Serial::search_window(&sorted, Serial::NAN));
+
+    // inserting at the returned index keeps the window sorted
+    let mut window = std::vec::Vec::new();
+    for n in [10, MAX_U16, 3, 0, MAX_U16 - 7, 5, 3] {
+        let serial = Serial::new_unchecked(n);
+        if let Err(i) = Serial::search_window(&window, serial) {
+            window.insert(i, serial);
+        }
+    }
+    assert_eq!(
+        [MAX_U16 - 7, MAX_U16, 0, 3, 5, 10].map(Serial::new_unchecked),
+        *window
+    );
+}
+
+#[test]
+fn format_into() {
+    let mut buf = [0; 5];
+    assert_eq!(Ok("0"), Serial::new_unchecked(0).format_into(&mut buf));
+    assert_eq!(
+        Ok("65534"),
+        Serial::new_unchecked(MAX_U16).format_into(&mut buf)
+    );
+    assert_eq!(Ok("NaN"), Serial::NAN.format_into(&mut buf));
+
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let mut buf = [0; 8];
+        assert_eq!(
+            Ok(&*std::format!("{n}")),
+            Serial::new_unchecked(n).format_into(&mut buf)
+        );
+    }
+
+    // too small
+    assert_eq!(
+        Err(BufferTooSmall),
+        Serial::new_unchecked(MAX_U16).format_into(&mut buf[..4])
+    );
+    assert_eq!(Err(BufferTooSmall), Serial::NAN.format_into(&mut buf[..2]));
+    assert_eq!(
+        Err(BufferTooSmall),
+        Serial::new_unchecked(0).format_into(&mut [])
+    );
+    assert_eq!(
+        Ok("10"),
+        Serial::new_unchecked(10).format_into(&mut buf[..2])
+    );
+
+    // bytes after the text are left alone, even if they are not UTF-8
+    let mut buf = [0xFF; 8];
+    assert_eq!(Ok("42"), Serial::new_unchecked(42).format_into(&mut buf));
+    assert_eq!([b'4', b'2', 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF], buf);
+}
+
+#[test]
+fn bounding_window() {
+    let range = |low: u16, high: u16| (Serial::new_unchecked(low), Serial::new_unchecked(high));
+    let window =
+        |low: u16, high: u16| Some((Serial::new_unchecked(low), Serial::new_unchecked(high)));
+
+    assert_eq!(None, Serial::bounding_window(&[]));
+    assert_eq!(window(5, 5), Serial::bounding_window(&[range(5, 5)]));
+    assert_eq!(window(5, 10), Serial::bounding_window(&[range(5, 10)]));
+
+    // overlapping and nested
+    assert_eq!(
+        window(5, 20),
+        Serial::bounding_window(&[range(5, 10), range(8, 20), range(12, 14)])
+    );
+
+    // disjoint
+    assert_eq!(
+        window(5, 40),
+        Serial::bounding_window(&[range(30, 40), range(5, 10)])
+    );
+
+    // straddling the point of wraparound
+    assert_eq!(
+        window(MAX_U16 - 5, 20),
+        Serial::bounding_window(&[range(10, 20), range(MAX_U16 - 5, 2)])
+    );
+    assert_eq!(
+        window(MAX_U16 - 5, 2),
+        Serial::bounding_window(&[range(0, 2), range(MAX_U16 - 5, MAX_U16)])
+    );
+
+    // the order of the ranges does not matter
+    assert_eq!(
+        window(MAX_U16 - 5, 20),
+        Serial::bounding_window(&[range(MAX_U16 - 5, 2), range(10, 20)])
+    );
+
+    // at the edge of the window
+    assert_eq!(
+        window(0, MID_U16),
+        Serial::bounding_window(&[range(0, 10), range(MID_U16 - 10, MID_U16)])
+    );
+    assert_eq!(
+        None,
+        Serial::bounding_window(&[range(0, 10), range(MID_U16 - 10, MID_U16 + 1)])
+    );
+
+    // spread around the ring, too wide for any window
+    assert_eq!(
+        None,
+        Serial::bounding_window(&[range(0, 10), range(20_000, 20_010), range(40_000, 40_010)])
+    );
+
+    // invalid ranges
+    assert_eq!(None, Serial::bounding_window(&[range(10, 5)]));
+    assert_eq!(None, Serial::bounding_window(&[range(5, NAN_U16)]));
+    assert_eq!(
+        None,
+        Serial::bounding_window(&[range(5, 10), range(NAN_U16, 10)])
+    );
+}
+
+#[test]
+fn spread() {
+    let count = |n: u16| NonZeroU16::new(n).unwrap();
+
+    assert_eq!(
+        [Serial::new_unchecked(5)],
+        *Serial::new_unchecked(5)
+            .spread(count(1))
+            .collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(
+        [
+            Serial::new_unchecked(MAX_U16),
+            Serial::new_unchecked(MID_U16 - 1)
+        ],
+        *Serial::new_unchecked(MAX_U16)
+            .spread(count(2))
+            .collect::<std::vec::Vec<_>>()
+    );
+    assert_eq!(0, Serial::NAN.spread(count(10)).count());
+
+    for n in [1, 2, 3, 7, 100, 1000, MID_U16, u16::MAX] {
+        for start in CANDIDATES.into_iter().filter(|&start| start != NAN_U16) {
+            let spread: std::vec::Vec<_> = Serial::new_unchecked(start).spread(count(n)).collect();
+            assert_eq!(usize::from(n), spread.len());
+            assert_eq!(Some(&Serial::new_unchecked(start)), spread.first());
+
+            // equidistant, except for the remainder from the last back to the first
+            let step = NAN_U16 / n;
+            for pair in spread.windows(2) {
+                assert_eq!((step, u16::MAX - step), pair[0].arcs(pair[1]).unwrap());
+            }
+            let (last, first) = (spread[spread.len() - 1], spread[0]);
+            if n > 1 {
+                assert_eq!(step + NAN_U16 % n, last.arcs(first).unwrap().0);
+            }
+        }
+    }
+}
+
+#[test]
+fn from_str() {
+    for n in CANDIDATES {
+        assert_eq!(Ok(Serial::new_unchecked(n)), std::format!("{n}").parse());
+    }
+    assert_eq!(Ok(Serial::NAN), "NaN".parse());
+    assert_eq!(Ok(Serial::NAN), "65535".parse());
+    assert_eq!(Err(ParseSerialError), "65536".parse::<Serial>());
+    assert_eq!(Err(ParseSerialError), "-1".parse::<Serial>());
+    assert_eq!(Err(ParseSerialError), "nan".parse::<Serial>());
+    assert_eq!(Err(ParseSerialError), "".parse::<Serial>());
+    assert_eq!(Err(ParseSerialError), "0x10".parse::<Serial>());
+}
+
+#[test]
+fn from_str_radix() {
+    assert_eq!(
+        Ok(Serial::new_unchecked(0x1A2B)),
+        Serial::from_str_radix("0x1A2B", 16)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0x1A2B)),
+        Serial::from_str_radix("1a2b", 16)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0b1011)),
+        Serial::from_str_radix("0b1011", 2)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0b1011)),
+        Serial::from_str_radix("1011", 2)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0o17)),
+        Serial::from_str_radix("0o17", 8)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(42)),
+        Serial::from_str_radix("42", 10)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(35)),
+        Serial::from_str_radix("z", 36)
+    );
+
+    // the NAN pattern
+    assert_eq!(Ok(Serial::NAN), Serial::from_str_radix("0xFFFF", 16));
+    assert_eq!(
+        Ok(Serial::NAN),
+        Serial::from_str_radix("0b1111111111111111", 2)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(MAX_U16)),
+        Serial::from_str_radix("0xFFFE", 16)
+    );
+
+    // out of range
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("0x10000", 16));
+    assert_eq!(
+        Err(ParseSerialError),
+        Serial::from_str_radix("0b10000000000000000", 2)
+    );
+
+    // prefixes only match their radix
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("0x10", 10));
+    assert_eq!(
+        Ok(Serial::new_unchecked(0x0B10)),
+        Serial::from_str_radix("0b10", 16)
+    );
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("0x", 16));
+
+    // prefixes are case-insensitive
+    assert_eq!(
+        Ok(Serial::new_unchecked(0x1A2B)),
+        Serial::from_str_radix("0X1A2B", 16)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0b1011)),
+        Serial::from_str_radix("0B1011", 2)
+    );
+    assert_eq!(
+        Ok(Serial::new_unchecked(0o17)),
+        Serial::from_str_radix("0O17", 8)
+    );
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("0X10", 10));
+
+    // no sign, also not after a prefix
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("+5", 10));
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("+A", 16));
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("0x+A", 16));
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("-5", 10));
+    assert_eq!(Err(ParseSerialError), "+5".parse::<Serial>());
+
+    // invalid radix
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("1", 1));
+    assert_eq!(Err(ParseSerialError), Serial::from_str_radix("1", 37));
+}
+
+#[test]
+fn min_max_propagate() {
+    let (a, b) = (Serial::new_unchecked(MAX_U16), Serial::new_unchecked(1));
+    assert_eq!(a, a.min_propagate(b));
+    assert_eq!(a, b.min_propagate(a));
+    assert_eq!(b, a.max_propagate(b));
+    assert_eq!(b, b.max_propagate(a));
+
+    assert_eq!(Serial::NAN, a.min_propagate(Serial::NAN));
+    assert_eq!(Serial::NAN, Serial::NAN.min_propagate(a));
+    assert_eq!(Serial::NAN, Serial::NAN.min_propagate(Serial::NAN));
+    assert_eq!(Serial::NAN, a.max_propagate(Serial::NAN));
+    assert_eq!(Serial::NAN, Serial::NAN.max_propagate(a));
+    assert_eq!(Serial::NAN, Serial::NAN.max_propagate(Serial::NAN));
+
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        for m in CANDIDATES.into_iter().filter(|&m| m != NAN_U16) {
+            let (a, b) = (Serial::new_unchecked(n), Serial::new_unchecked(m));
+            assert_eq!(a.min(b), a.min_propagate(b));
+            assert_eq!(a.max(b), a.max_propagate(b));
+        }
+    }
+}
+
+#[test]
+fn successors() {
+    let mut successors = Serial::new_unchecked(MAX_U16 - 1).successors();
+    assert_eq!(Some(Serial::new_unchecked(MAX_U16)), successors.next());
+    assert_eq!(Some(Serial::new_unchecked(0)), successors.next());
+    assert_eq!(Some(Serial::new_unchecked(1)), successors.next());
+    assert_eq!((usize::MAX, None), successors.size_hint());
+
+    // fused: keeps returning `None` after NAN
+    let mut successors = Serial::NAN.successors();
+    assert_eq!((0, Some(0)), successors.size_hint());
+    for _ in 0..5 {
+        assert_eq!(None, successors.next());
+    }
+
+    // works in combinators that rely on `FusedIterator`
+    fn assert_fused<I: core::iter::FusedIterator>(_: &I) {}
+    assert_fused(&successors);
+    let chained: std::vec::Vec<_> = Serial::NAN
+        .successors()
+        .chain(Serial::new_unchecked(5).successors().take(2))
+        .collect();
+    assert_eq!(
+        [Serial::new_unchecked(6), Serial::new_unchecked(7)],
+        *chained
+    );
+
+    for n in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let serial = Serial::new_unchecked(n);
+        assert!(serial
+            .successors()
+            .take(70_000)
+            .zip(core::iter::successors(Some(serial), |s| Some(*s + 1)).skip(1))
+            .all(|(a, b)| a == b));
+    }
+}
+
+#[test]
+fn from_hash() {
+    assert_eq!(Serial::new_unchecked(0), Serial::from_hash(0));
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::from_hash(u64::from(MAX_U16))
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::from_hash(u64::from(NAN_U16))
+    );
+    assert_eq!(Serial::new_unchecked(0), Serial::from_hash(u64::MAX));
+
+    let mut h: u64 = 0x1234_5678;
+    for _ in 0..100_000 {
+        h = h.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1);
+        assert!(!Serial::from_hash(h).is_nan());
+    }
+    for n in CANDIDATES {
+        assert!(!Serial::from_hash(u64::from(n)).is_nan());
+        assert!(!Serial::from_hash(u64::from(n) << 48).is_nan());
+    }
+}
+
+#[test]
+fn window_position() {
+    let low = Serial::new_unchecked(MAX_U16 - 2);
+    assert_eq!(Some(0), low.window_position(low));
+    assert_eq!(Some(3), Serial::new_unchecked(0).window_position(low));
+
+    // the far edge
+    assert_eq!(Some(MID_U16), (low + MID_U16).window_position(low));
+    assert_eq!(None, (low + MID_U16 + 1).window_position(low));
+    assert_eq!(None, low.wrapping_sub(1).window_position(low));
+
+    assert_eq!(None, Serial::NAN.window_position(low));
+    assert_eq!(None, low.window_position(Serial::NAN));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_flatten() {
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Inner {
+        serial: Serial,
+    }
+
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Outer {
+        name: std::string::String,
+        #[serde(flatten)]
+        inner: Inner,
+    }
+
+    #[derive(Debug, PartialEq, ::serde::Deserialize, ::serde::Serialize)]
+    struct Config {
+        #[serde(flatten)]
+        serials: std::collections::BTreeMap<std::string::String, Serial>,
+    }
+
+    for n in CANDIDATES {
+        let expected = Outer {
+            name: "a".into(),
+            inner: Inner {
+                serial: Serial::new_unchecked(n),
+            },
+        };
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(std::format!(r#"{{"name":"a","serial":{n}}}"#), json);
+        assert_eq!(expected, serde_json::from_str(&json).unwrap());
+
+        let expected = Config {
+            serials: [
+                ("a".into(), Serial::new_unchecked(n)),
+                ("b".into(), Serial::new_unchecked(1)),
+            ]
+            .into(),
+        };
+        let json = serde_json::to_string(&expected).unwrap();
+        assert_eq!(std::format!(r#"{{"a":{n},"b":1}}"#), json);
+        assert_eq!(expected, serde_json::from_str(&json).unwrap());
+    }
+}
+
+#[test]
+fn increase_all() {
+    let mut serials = CANDIDATES.map(Serial::new_unchecked);
+    Serial::increase_all(&mut serials);
+    for (n, serial) in CANDIDATES.into_iter().zip(serials) {
+        let mut expected = Serial::new_unchecked(n);
+        expected.increase();
+        assert_eq!(expected, serial);
+    }
+
+    let mut mixed = [
+        Serial::new_unchecked(MAX_U16),
+        Serial::NAN,
+        Serial::new_unchecked(5),
+    ];
+    Serial::increase_all(&mut mixed);
+    assert_eq!(
+        [
+            Serial::new_unchecked(0),
+            Serial::NAN,
+            Serial::new_unchecked(6)
+        ],
+        mixed
+    );
+
+    Serial::increase_all(&mut []);
+}
+
+#[test]
+fn serial_range() {
+    let range =
+        SerialRange::new(Serial::new_unchecked(MAX_U16 - 2), Serial::new_unchecked(2)).unwrap();
+    assert_eq!(Serial::new_unchecked(MAX_U16 - 2), range.first());
+    assert_eq!(Serial::new_unchecked(2), range.last());
+    assert_eq!(5, range.span());
+    for n in [MAX_U16 - 2, MAX_U16, 0, 2] {
+        assert!(range.contains(Serial::new_unchecked(n)));
+    }
+    for n in [MAX_U16 - 3, 3, MID_U16, NAN_U16] {
+        assert!(!range.contains(Serial::new_unchecked(n)));
+    }
+
+    let single = SerialRange::new(Serial::new_unchecked(5), Serial::new_unchecked(5)).unwrap();
+    assert_eq!(0, single.span());
+    assert!(single.contains(Serial::new_unchecked(5)));
+    assert!(!single.contains(Serial::new_unchecked(6)));
+
+    // at the edge of the window
+    assert!(SerialRange::new(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16)).is_some());
+    assert!(
+        SerialRange::new(Serial::new_unchecked(0), Serial::new_unchecked(MID_U16 + 1)).is_none()
+    );
+
+    assert!(SerialRange::new(Serial::new_unchecked(2), Serial::new_unchecked(1)).is_none());
+    assert!(SerialRange::new(Serial::NAN, Serial::new_unchecked(1)).is_none());
+    assert!(SerialRange::new(Serial::new_unchecked(1), Serial::NAN).is_none());
+}
+
+#[test]
+fn ring_halves() {
+    assert_eq!(None, Serial::ring_halves(Serial::NAN));
+
+    for pivot in CANDIDATES.into_iter().filter(|&n| n != NAN_U16) {
+        let pivot = Serial::new_unchecked(pivot);
+        let (before, after) = Serial::ring_halves(pivot).unwrap();
+        assert_eq!(pivot, after.first());
+        assert_eq!(pivot.wrapping_sub(1), before.last());
+        assert_eq!(
+            u32::from(NAN_U16),
+            u32::from(before.span()) + 1 + u32::from(after.span()) + 1
+        );
+
+        for n in 0..NAN_U16 {
+            let serial = Serial::new_unchecked(n);
+            assert_ne!(before.contains(serial), after.contains(serial));
+            assert_eq!(serial.precedes(pivot), before.contains(serial));
+            assert_eq!(serial.succeeds_or_eq(pivot), after.contains(serial));
+        }
+        assert!(!before.contains(Serial::NAN));
+        assert!(!after.contains(Serial::NAN));
+    }
+}
+
+#[test]
+fn try_from_i32() {
+    for n in CANDIDATES {
+        assert_eq!(Ok(Serial::new_unchecked(n)), Serial::try_from(i32::from(n)));
+    }
+    assert_eq!(Ok(Serial::new_unchecked(0)), Serial::try_from(0));
+    assert_eq!(Ok(Serial::new_unchecked(MAX_U16)), Serial::try_from(65534));
+    assert_eq!(Ok(Serial::NAN), Serial::try_from(65535));
+    assert_eq!(Ok(Serial::NAN), Serial::try_from(-1));
+
+    assert_eq!(Err(SerialRangeError), Serial::try_from(65536));
+    assert_eq!(Err(SerialRangeError), Serial::try_from(-2));
+    assert_eq!(Err(SerialRangeError), Serial::try_from(i32::MAX));
+    assert_eq!(Err(SerialRangeError), Serial::try_from(i32::MIN));
+}
+
+#[test]
+fn approx_eq() {
+    let cases = [
+        (Serial::new_unchecked(5), Serial::new_unchecked(5), 0, true),
+        (Serial::new_unchecked(5), Serial::new_unchecked(6), 0, false),
+        (Serial::new_unchecked(5), Serial::new_unchecked(6), 1, true),
+        (Serial::new_unchecked(6), Serial::new_unchecked(5), 1, true),
+        (
+            Serial::new_unchecked(MAX_U16),
+            Serial::new_unchecked(1),
+            1,
+            false,
+        ),
+        (
+            Serial::new_unchecked(MAX_U16),
+            Serial::new_unchecked(1),
+            2,
+            true,
+        ),
+        (
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(MID_U16),
+            MID_U16,
+            true,
+        ),
+        (
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(MID_U16),
+            MID_U16 - 1,
+            false,
+        ),
+        (Serial::NAN, Serial::NAN, 0, true),
+        (Serial::NAN, Serial::new_unchecked(0), MID_U16, false),
+        (Serial::new_unchecked(0), Serial::NAN, u16::MAX, false),
+    ];
+    for (a, b, tolerance, expected) in cases {
+        assert_eq!(
+            expected,
+            a.approx_eq(b, tolerance),
+            "{a:?} {b:?} {tolerance}"
+        );
+    }
+}
+
+#[test]
+#[cfg(feature = "bytemuck")]
+fn bytemuck_no_uninit() {
+    fn write_bytes<T: bytemuck::NoUninit>(sink: &mut [u8], items: &[T]) -> usize {
+        let bytes: &[u8] = bytemuck::cast_slice(items);
+        sink.get_mut(..bytes.len()).unwrap().copy_from_slice(bytes);
+        bytes.len()
+    }
+
+    let serials = [
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::NAN,
+    ];
+    let mut sink = [0_u8; 8];
+    assert_eq!(6, write_bytes(&mut sink, &serials));
+
+    let mut expected = [0_u8; 6];
+    for (chunk, n) in expected.chunks_exact_mut(2).zip([1_u16, MAX_U16, NAN_U16]) {
+        chunk.copy_from_slice(&n.to_ne_bytes());
+    }
+    assert_eq!(expected, sink[..6]);
+    assert_eq!([0, 0], sink[6..]);
+}
+
+#[test]
+fn expected_next() {
+    assert_eq!(Serial::ZERO, Serial::expected_next(Serial::NAN));
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::expected_next(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::expected_next(Serial::new_unchecked(MAX_U16))
+    );
+
+    let mut last_seen = Serial::NAN;
+    for _ in 0..3 {
+        let next = Serial::expected_next(last_seen);
+        assert!(last_seen.is_nan() || next.is_next_after(last_seen));
+        last_seen = next;
+    }
+    assert_eq!(Serial::new_unchecked(2), last_seen);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn coalesce() {
+    let range = |first, last| {
+        SerialRange::new(Serial::new_unchecked(first), Serial::new_unchecked(last)).unwrap()
+    };
+
+    assert_eq!(Vec::<SerialRange>::new(), Serial::coalesce(&[]));
+    assert_eq!(
+        Vec::<SerialRange>::new(),
+        Serial::coalesce(&[Serial::NAN, Serial::NAN])
+    );
+
+    // contiguous
+    let serials = [
+        Serial::new_unchecked(3),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(0),
+    ];
+    assert_eq!(std::vec![range(0, 3)], Serial::coalesce(&serials));
+
+    // gapped, with duplicates and NAN
+    let serials = [
+        Serial::new_unchecked(7),
+        Serial::new_unchecked(1),
+        Serial::NAN,
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(6),
+    ];
+    assert_eq!(
+        std::vec![range(1, 2), range(5, 7)],
+        Serial::coalesce(&serials)
+    );
+
+    // straddles the wraparound
+    let serials = [
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(4),
+    ];
+    assert_eq!(
+        std::vec![range(MAX_U16 - 1, 1), range(4, 4)],
+        Serial::coalesce(&serials)
+    );
+}
+
+#[test]
+fn is_stale() {
+    let high_water = Serial::new_unchecked(1);
+    assert!(Serial::new_unchecked(0).is_stale(high_water, 3));
+    assert!(Serial::new_unchecked(MAX_U16).is_stale(high_water, 3));
+    assert!(Serial::new_unchecked(MAX_U16 - 1).is_stale(high_water, 3));
+    assert!(!Serial::new_unchecked(MAX_U16 - 2).is_stale(high_water, 3));
+
+    assert!(!high_water.is_stale(high_water, 3));
+    assert!(!Serial::new_unchecked(2).is_stale(high_water, 3));
+    assert!(!Serial::new_unchecked(2).is_stale(high_water, u16::MAX));
+
+    assert!(!Serial::NAN.is_stale(high_water, u16::MAX));
+    assert!(!Serial::new_unchecked(0).is_stale(Serial::NAN, u16::MAX));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_bounded() {
+    #[derive(::serde::Deserialize, ::serde::Serialize)]
+    struct Packet {
+        #[serde(with = "crate::serde::bounded")]
+        serial: Serial,
+    }
+
+    let from = |json: &str| serde_json::from_str::<Packet>(json).map(|p| p.serial);
+
+    assert_eq!(
+        Serial::new_unchecked(0),
+        from(r#"{ "serial": 0 }"#).unwrap()
+    );
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        from(r#"{ "serial": 65534 }"#).unwrap()
+    );
+    assert_eq!(Serial::NAN, from(r#"{ "serial": 65535 }"#).unwrap());
+
+    let err = from(r#"{ "serial": 70000 }"#).unwrap_err();
+    assert!(
+        std::string::ToString::to_string(&err)
+            .starts_with("serial number 70000 is out of range, expected 0..=65535"),
+        "{err}"
+    );
+
+    let err = from(r#"{ "serial": -1 }"#).unwrap_err();
+    assert!(
+        std::string::ToString::to_string(&err)
+            .starts_with("serial number -1 is out of range, expected 0..=65535"),
+        "{err}"
+    );
+
+    assert!(from(r#"{ "serial": "42" }"#).is_err());
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        let encoded = serde_json::to_string(&Packet { serial: expected }).unwrap();
+        assert_eq!(expected, from(&encoded).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serde_bounded_binary() {
+    #[derive(::serde::Deserialize, ::serde::Serialize)]
+    struct Packet {
+        #[serde(with = "crate::serde::bounded")]
+        serial: Serial,
+        tail: u8,
+    }
+
+    let cfg = bincode::config::standard().with_fixed_int_encoding();
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        let packet = Packet {
+            serial: expected,
+            tail: 7,
+        };
+
+        // two bytes for the serial number, one for the tail
+        let encoded = bincode::serde::encode_to_vec(&packet, cfg).unwrap();
+        assert_eq!(3, encoded.len());
+        let (decoded, len): (Packet, _) = bincode::serde::decode_from_slice(&encoded, cfg).unwrap();
+        assert_eq!(3, len);
+        assert_eq!((expected, 7), (decoded.serial, decoded.tail));
+
+        let mut buf = [0_u8; 4];
+        let encoded = postcard::to_slice(&packet, &mut buf).unwrap();
+        let decoded: Packet = postcard::from_bytes(encoded).unwrap();
+        assert_eq!((expected, 7), (decoded.serial, decoded.tail));
+    }
+}
+
+#[test]
+fn window_position_in_cycle() {
+    let epoch_start = Serial::new_unchecked(MAX_U16 - 9);
+
+    // at the epoch boundary
+    assert_eq!(Some(0), epoch_start.window_position(epoch_start));
+    assert_eq!(
+        None,
+        Serial::new_unchecked(MAX_U16 - 10).window_position(epoch_start)
+    );
+
+    // mid-cycle, across the wraparound
+    assert_eq!(
+        Some(9),
+        Serial::new_unchecked(MAX_U16).window_position(epoch_start)
+    );
+    assert_eq!(
+        Some(10),
+        Serial::new_unchecked(0).window_position(epoch_start)
+    );
+    assert_eq!(
+        Some(15),
+        Serial::new_unchecked(5).window_position(epoch_start)
+    );
+
+    // the last index of the cycle
+    let last = epoch_start.wrapping_add(MID_U16);
+    assert_eq!(Some(MID_U16), last.window_position(epoch_start));
+    assert_eq!(None, last.successor().window_position(epoch_start));
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn distance_histogram() {
+    let serials = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0), // wraparound
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(3),
+        Serial::NAN,
+        Serial::new_unchecked(10),
+        Serial::new_unchecked(MID_U16 + 10),
+    ];
+    // distances: 1, 1, 2, 1, skipped, skipped, 32767
+
+    assert_eq!(std::vec![5], Serial::distance_histogram(&serials, 0));
+    assert_eq!(std::vec![4, 1], Serial::distance_histogram(&serials, 1));
+
+    let fine = Serial::distance_histogram(&serials, 15);
+    assert_eq!(32768, fine.len());
+    assert_eq!(Some(&3), fine.get(1));
+    assert_eq!(Some(&1), fine.get(usize::from(MID_U16)));
+    assert_eq!(Some(&1), fine.get(2));
+    assert_eq!(5, fine.iter().sum::<u32>());
+    assert_eq!(fine, Serial::distance_histogram(&serials, u8::MAX));
+
+    assert_eq!(std::vec![0, 0], Serial::distance_histogram(&[], 1));
+    assert_eq!(
+        std::vec![0, 0],
+        Serial::distance_histogram(&[Serial::new_unchecked(1)], 1)
+    );
+}
+
+#[test]
+fn normalize_against() {
+    let anchor = Serial::new_unchecked(MAX_U16 - 2);
+    let serials = [
+        Serial::new_unchecked(MAX_U16 - 2),
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(100),
+    ];
+    let mut out = [0; 6];
+    Serial::normalize_against(&serials, anchor, &mut out);
+    assert_eq!([0, 1, 2, 3, 4, 103], out);
+    assert!(out.windows(2).all(|pair| pair[0] < pair[1]));
+
+    let serials = [anchor.wrapping_sub(1), Serial::NAN, anchor];
+    let mut out = [7; 4];
+    Serial::normalize_against(&serials, anchor, &mut out);
+    assert_eq!([u16::MAX, u16::MAX, 0, 7], out);
+
+    let mut out = [7; 1];
+    Serial::normalize_against(&serials, Serial::NAN, &mut out);
+    assert_eq!([u16::MAX], out);
+}
+
+#[test]
+fn increase_report() {
+    let mut serial = Serial::new_unchecked(MAX_U16);
+    assert_eq!(IncreaseOutcome::Wrapped, serial.increase_report());
+    assert_eq!(Serial::new_unchecked(0), serial);
+
+    assert_eq!(IncreaseOutcome::Advanced, serial.increase_report());
+    assert_eq!(Serial::new_unchecked(1), serial);
+
+    let mut serial = Serial::new_unchecked(MAX_U16 - 1);
+    assert_eq!(IncreaseOutcome::Advanced, serial.increase_report());
+    assert_eq!(Serial::new_unchecked(MAX_U16), serial);
+
+    let mut serial = Serial::NAN;
+    assert_eq!(IncreaseOutcome::WasNan, serial.increase_report());
+    assert_eq!(Serial::NAN, serial);
+
+    for n in CANDIDATES {
+        let mut expected = Serial::new_unchecked(n);
+        expected.increase();
+        let mut actual = Serial::new_unchecked(n);
+        let _ = actual.increase_report();
+        assert_eq!(expected, actual);
+    }
+}
+
+#[test]
+fn parse_lenient() {
+    for token in [
+        "", "-", "NaN", "nan", "none", "None", "NONE", "null", "Null", "NULL",
+    ] {
+        assert_eq!(Ok(Serial::NAN), Serial::parse_lenient(token), "{token:?}");
+    }
+    assert_eq!(Ok(Serial::new_unchecked(0)), Serial::parse_lenient("0"));
+    assert_eq!(
+        Ok(Serial::new_unchecked(MAX_U16)),
+        Serial::parse_lenient("65534")
+    );
+    assert_eq!(Ok(Serial::NAN), Serial::parse_lenient("65535"));
+
+    for garbage in ["nil", "--", " ", "n/a", "-1", "65536", "0x10"] {
+        assert_eq!(
+            Err(ParseSerialError),
+            Serial::parse_lenient(garbage),
+            "{garbage:?}"
+        );
+    }
+
+    // the FromStr implementation stays strict
+    assert_eq!(Err(ParseSerialError), "".parse::<Serial>());
+    assert_eq!(Err(ParseSerialError), "none".parse::<Serial>());
+}
+
+#[test]
+fn cmp_by_window() {
+    let anchor = Serial::new_unchecked(MAX_U16 - 1);
+    let mut serials = [
+        Serial::new_unchecked(3),
+        Serial::NAN,
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(1),
+    ];
+    serials.sort_by(crate::cmp::by_window(anchor));
+    assert_eq!(
+        [
+            Serial::new_unchecked(MAX_U16 - 1),
+            Serial::new_unchecked(MAX_U16),
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(1),
+            Serial::new_unchecked(3),
+            Serial::NAN,
+        ],
+        serials
+    );
+
+    // agrees with partial_cmp in the window after the anchor
+    let by_window = crate::cmp::by_window(anchor);
+    for &a in serials.iter().filter(|serial| !serial.is_nan()) {
+        for &b in serials.iter().filter(|serial| !serial.is_nan()) {
+            assert_eq!(Serial::partial_cmp(a, b), Some(by_window(&a, &b)));
+        }
+    }
+
+    // numbers before the anchor are sorted after all others
+    let mut serials = [
+        Serial::new_unchecked(MAX_U16 - 2),
+        Serial::new_unchecked(0),
+        Serial::NAN,
+    ];
+    serials.sort_by(crate::cmp::by_window(anchor));
+    assert_eq!(
+        [
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(MAX_U16 - 2),
+            Serial::NAN
+        ],
+        serials
+    );
+
+    // a NAN anchor sorts by counter
+    let mut serials = [
+        Serial::new_unchecked(MAX_U16),
+        Serial::NAN,
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MID_U16),
+    ];
+    serials.sort_by(crate::cmp::by_window(Serial::NAN));
+    assert_eq!(
+        [
+            Serial::new_unchecked(0),
+            Serial::new_unchecked(MID_U16),
+            Serial::new_unchecked(MAX_U16),
+            Serial::NAN
+        ],
+        serials
+    );
+}
+
+#[test]
+fn midpoint_forward() {
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(0).midpoint_forward(Serial::new_unchecked(10))
+    );
+    assert_eq!(
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(0).midpoint_forward(Serial::new_unchecked(11))
+    ); // rounded down
+    assert_eq!(
+        Serial::new_unchecked(7),
+        Serial::new_unchecked(7).midpoint_forward(Serial::new_unchecked(7))
+    );
+    assert_eq!(
+        Serial::new_unchecked(7),
+        Serial::new_unchecked(7).midpoint_forward(Serial::new_unchecked(8))
+    );
+
+    // the forward arc wraps around
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(MAX_U16 - 2).midpoint_forward(Serial::new_unchecked(1))
+    );
+
+    // the forward arc is the longer one: 10 -> 65534 -> 0
+    let mid = Serial::new_unchecked(10).midpoint_forward(Serial::new_unchecked(0));
+    assert_eq!(Serial::new_unchecked(10 + 32762), mid);
+    assert!(mid.succeeds(Serial::new_unchecked(10)));
+    assert_ne!(Serial::new_unchecked(5), mid); // the midpoint of the shorter arc
+
+    assert!(Serial::NAN
+        .midpoint_forward(Serial::new_unchecked(0))
+        .is_nan());
+    assert!(Serial::new_unchecked(0)
+        .midpoint_forward(Serial::NAN)
+        .is_nan());
+    assert!(Serial::NAN.midpoint_forward(Serial::NAN).is_nan());
+}
+
+#[test]
+fn to_fixed_decimal() {
+    let mut buf = [b'_'; 5];
+    assert_eq!(1, Serial::new_unchecked(0).to_fixed_decimal(&mut buf));
+    assert_eq!(b"0____", &buf);
+
+    let mut buf = [b'_'; 5];
+    assert_eq!(5, Serial::new_unchecked(MAX_U16).to_fixed_decimal(&mut buf));
+    assert_eq!(b"65534", &buf);
+
+    let mut buf = [b'_'; 5];
+    assert_eq!(3, Serial::NAN.to_fixed_decimal(&mut buf));
+    assert_eq!(b"NaN__", &buf);
+
+    for n in CANDIDATES {
+        let mut buf = [0; 5];
+        let len = Serial::new_unchecked(n).to_fixed_decimal(&mut buf);
+        let mut expected = [0; 5];
+        assert_eq!(
+            Ok(&buf[..len]),
+            Serial::new_unchecked(n)
+                .format_into(&mut expected)
+                .map(str::as_bytes)
+        );
+    }
+}
+
+#[test]
+fn diff_checked() {
+    assert_eq!(
+        Some(0),
+        Serial::new_unchecked(0).diff_checked(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(-1),
+        Serial::new_unchecked(MAX_U16).diff_checked(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(1),
+        Serial::new_unchecked(0).diff_checked(Serial::new_unchecked(MAX_U16))
+    );
+    assert_eq!(
+        Some(-32766),
+        Serial::new_unchecked(0).diff_checked(Serial::new_unchecked(MID_U16 - 1))
+    );
+    assert_eq!(
+        Some(32766),
+        Serial::new_unchecked(MID_U16 - 1).diff_checked(Serial::new_unchecked(0))
+    );
+
+    // at the window edge
+    assert_eq!(
+        None,
+        Serial::new_unchecked(0).diff_checked(Serial::new_unchecked(MID_U16))
+    );
+    assert_eq!(
+        None,
+        Serial::new_unchecked(MID_U16).diff_checked(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        None,
+        Serial::new_unchecked(0).diff_checked(Serial::new_unchecked(MID_U16 + 1))
+    );
+
+    assert_eq!(None, Serial::new_unchecked(0).diff_checked(Serial::NAN));
+    assert_eq!(None, Serial::NAN.diff_checked(Serial::new_unchecked(0)));
+    assert_eq!(None, Serial::NAN.diff_checked(Serial::NAN));
+}
+
+#[test]
+fn rank_in() {
+    let window = [
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::NAN,
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(5),
+    ];
+    assert_eq!(Some(0), Serial::new_unchecked(MAX_U16 - 1).rank_in(&window));
+    assert_eq!(Some(1), Serial::new_unchecked(MAX_U16).rank_in(&window));
+    assert_eq!(Some(2), Serial::new_unchecked(0).rank_in(&window));
+    assert_eq!(Some(3), Serial::new_unchecked(2).rank_in(&window));
+    assert_eq!(Some(4), Serial::new_unchecked(5).rank_in(&window));
+
+    assert_eq!(None, Serial::new_unchecked(1).rank_in(&window));
+    assert_eq!(None, Serial::NAN.rank_in(&window));
+    assert_eq!(None, Serial::new_unchecked(0).rank_in(&[]));
+
+    // duplicates are counted
+    let window = [
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(2),
+    ];
+    assert_eq!(Some(0), Serial::new_unchecked(1).rank_in(&window));
+    assert_eq!(Some(2), Serial::new_unchecked(2).rank_in(&window));
+}
+
+#[test]
+#[cfg(target_has_atomic = "16")]
+fn assign_next() {
+    use core::sync::atomic::{AtomicU16, Ordering};
+
+    let counter = AtomicU16::new(MAX_U16);
+    assert_eq!(
+        Serial::new_unchecked(MAX_U16),
+        Serial::assign_next(&counter, Ordering::SeqCst)
+    );
+    assert_eq!(
+        Serial::new_unchecked(0),
+        Serial::assign_next(&counter, Ordering::AcqRel)
+    );
+    assert_eq!(
+        Serial::new_unchecked(1),
+        Serial::assign_next(&counter, Ordering::Release)
+    );
+    assert_eq!(2, counter.load(Ordering::Relaxed));
+
+    let counter = AtomicU16::new(NAN_U16);
+    assert_eq!(
+        Serial::NAN,
+        Serial::assign_next(&counter, Ordering::Relaxed)
+    );
+    assert_eq!(NAN_U16, counter.load(Ordering::Relaxed));
+
+    // concurrent calls assign distinct numbers
+    const THREADS: usize = 8;
+    const PER_THREAD: usize = 2000;
+    let counter = AtomicU16::new(MAX_U16 - 100);
+    let mut assigned: std::vec::Vec<Serial> = std::thread::scope(|scope| {
+        let handles: std::vec::Vec<_> = (0..THREADS)
+            .map(|_| {
+                scope.spawn(|| {
+                    (0..PER_THREAD)
+                        .map(|_| Serial::assign_next(&counter, Ordering::Relaxed))
+                        .collect::<std::vec::Vec<_>>()
+                })
+            })
+            .collect();
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect()
+    });
+    assigned.sort_unstable_by_key(|serial| serial.0);
+    assigned.dedup();
+    assert_eq!(THREADS * PER_THREAD, assigned.len());
+    assert!(!assigned.contains(&Serial::NAN));
+}
+
+#[test]
+fn raw_cmp() {
+    use core::cmp::Ordering::{Equal, Greater, Less};
+
+    assert_eq!(
+        Less,
+        Serial::new_unchecked(0).raw_cmp(Serial::new_unchecked(1))
+    );
+    assert_eq!(
+        Equal,
+        Serial::new_unchecked(1).raw_cmp(Serial::new_unchecked(1))
+    );
+    assert_eq!(
+        Greater,
+        Serial::new_unchecked(MID_U16 + 1).raw_cmp(Serial::new_unchecked(0))
+    );
+    assert_eq!(Less, Serial::new_unchecked(MAX_U16).raw_cmp(Serial::NAN));
+    assert_eq!(Equal, Serial::NAN.raw_cmp(Serial::NAN));
+
+    // partial_cmp disagrees across the wraparound
+    assert_eq!(
+        Greater,
+        Serial::new_unchecked(MAX_U16).raw_cmp(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(Less),
+        Serial::new_unchecked(MAX_U16).partial_cmp(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        Some(Less),
+        Serial::new_unchecked(MID_U16 + 1).partial_cmp(Serial::new_unchecked(0))
+    );
+
+    // sorts numerically
+    let mut serials = CANDIDATES.map(Serial::new_unchecked);
+    serials.reverse();
+    serials.sort_by(|a, b| a.raw_cmp(*b));
+    assert!(serials.windows(2).all(|pair| pair[0].0 <= pair[1].0));
+}
+
+#[test]
+fn pack_with_epoch() {
+    assert_eq!(0, Serial::new_unchecked(0).pack_with_epoch(0));
+    assert_eq!(
+        0xFFFF_FFFE,
+        Serial::new_unchecked(MAX_U16).pack_with_epoch(u16::MAX)
+    );
+    assert_eq!(0x0001_FFFF, Serial::NAN.pack_with_epoch(1));
+
+    // monotonic across a wraparound
+    let before = Serial::new_unchecked(MAX_U16).pack_with_epoch(7);
+    let after = Serial::new_unchecked(MAX_U16)
+        .successor()
+        .pack_with_epoch(8);
+    assert!(before < after);
+
+    for epoch in [0, 1, 0x1234, u16::MAX] {
+        for n in CANDIDATES {
+            let packed = Serial::new_unchecked(n).pack_with_epoch(epoch);
+            assert_eq!(
+                (epoch, Serial::new_unchecked(n)),
+                Serial::unpack_with_epoch(packed)
+            );
+        }
+    }
+}
+
+#[test]
+fn catch_up_to() {
+    let mut serial = Serial::new_unchecked(MAX_U16 - 1);
+    assert_eq!(3, serial.catch_up_to(Serial::new_unchecked(1)));
+    assert_eq!(Serial::new_unchecked(1), serial);
+
+    // already there, or ahead
+    assert_eq!(0, serial.catch_up_to(Serial::new_unchecked(1)));
+    assert_eq!(0, serial.catch_up_to(Serial::new_unchecked(0)));
+    assert_eq!(0, serial.catch_up_to(Serial::new_unchecked(MAX_U16)));
+    assert_eq!(Serial::new_unchecked(1), serial);
+
+    // as far as the window goes
+    assert_eq!(
+        MID_U16,
+        serial.catch_up_to(Serial::new_unchecked(1 + MID_U16))
+    );
+    assert_eq!(Serial::new_unchecked(1 + MID_U16), serial);
+
+    assert_eq!(0, serial.catch_up_to(Serial::NAN));
+    assert_eq!(Serial::new_unchecked(1 + MID_U16), serial);
+
+    let mut nan = Serial::NAN;
+    assert_eq!(0, nan.catch_up_to(Serial::new_unchecked(0)));
+    assert!(nan.is_nan());
+}
+
+#[test]
+fn serial_map() {
+    let mut map: SerialMap<u32, 4> = SerialMap::default();
+    assert!(map.base().is_nan());
+    assert_eq!(None, map.get(Serial::new_unchecked(0)));
+    assert_eq!(Err(1), map.insert(Serial::NAN, 1));
+
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(MAX_U16 - 1), 1));
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(MAX_U16), 2));
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(0), 3));
+    assert_eq!(Ok(Some(3)), map.insert(Serial::new_unchecked(0), 30));
+    assert_eq!(Serial::new_unchecked(MAX_U16 - 1), map.base());
+    assert_eq!(Some(&1), map.get(Serial::new_unchecked(MAX_U16 - 1)));
+    assert_eq!(Some(&2), map.get(Serial::new_unchecked(MAX_U16)));
+    assert_eq!(Some(&30), map.get(Serial::new_unchecked(0)));
+    assert_eq!(None, map.get(Serial::new_unchecked(1)));
+
+    // evicts the oldest entries across the wraparound
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(3), 4));
+    assert_eq!(Serial::new_unchecked(0), map.base());
+    assert_eq!(None, map.get(Serial::new_unchecked(MAX_U16 - 1)));
+    assert_eq!(None, map.get(Serial::new_unchecked(MAX_U16)));
+    assert_eq!(Some(&30), map.get(Serial::new_unchecked(0)));
+    assert_eq!(Some(&4), map.get(Serial::new_unchecked(3)));
+    assert_eq!(Err(5), map.insert(Serial::new_unchecked(MAX_U16), 5)); // too old
+
+    // slots are reused after the head moved
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(1), 6));
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(2), 7));
+    if let Some(value) = map.get_mut(Serial::new_unchecked(2)) {
+        *value += 1;
+    }
+    assert_eq!(
+        [Some(&30), Some(&6), Some(&8), Some(&4)],
+        [0, 1, 2, 3].map(|n| map.get(Serial::new_unchecked(n)))
+    );
+
+    assert_eq!(Some(6), map.remove(Serial::new_unchecked(1)));
+    assert_eq!(None, map.remove(Serial::new_unchecked(1)));
+
+    // a jump far ahead drops everything
+    assert_eq!(Ok(None), map.insert(Serial::new_unchecked(1000), 9));
+    assert_eq!(Serial::new_unchecked(997), map.base());
+    assert_eq!(
+        [None, None, None, Some(&9)],
+        [997, 998, 999, 1000].map(|n| map.get(Serial::new_unchecked(n)))
+    );
+
+    let mut empty: SerialMap<u32, 0> = SerialMap::new();
+    assert_eq!(Err(1), empty.insert(Serial::new_unchecked(0), 1));
+    assert_eq!(None, empty.get(Serial::new_unchecked(0)));
+}
+
+#[test]
+fn validate_increasing() {
+    let strict = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(1),
+    ];
+    assert_eq!(Ok(()), Serial::validate_increasing(&strict, false));
+    assert_eq!(Ok(()), Serial::validate_increasing(&strict, true));
+
+    let gapped = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(5),
+        Serial::new_unchecked(MID_U16 + 5),
+    ];
+    assert_eq!(Err(1), Serial::validate_increasing(&gapped, false));
+    assert_eq!(Ok(()), Serial::validate_increasing(&gapped, true));
+
+    let out_of_order = [
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(2),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(3),
+    ];
+    assert_eq!(Err(1), Serial::validate_increasing(&out_of_order, false));
+    assert_eq!(Err(2), Serial::validate_increasing(&out_of_order, true));
+
+    let duplicate = [
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(0),
+    ];
+    assert_eq!(Err(2), Serial::validate_increasing(&duplicate, false));
+    assert_eq!(Err(2), Serial::validate_increasing(&duplicate, true));
+
+    // too far ahead to succeed
+    let jump = [Serial::new_unchecked(0), Serial::new_unchecked(MID_U16 + 1)];
+    assert_eq!(Err(1), Serial::validate_increasing(&jump, true));
+
+    assert_eq!(Err(0), Serial::validate_increasing(&[Serial::NAN], true));
+    assert_eq!(
+        Err(0),
+        Serial::validate_increasing(&[Serial::NAN, Serial::new_unchecked(0)], true)
+    );
+    assert_eq!(
+        Err(1),
+        Serial::validate_increasing(&[Serial::new_unchecked(0), Serial::NAN], true)
+    );
+    assert_eq!(Ok(()), Serial::validate_increasing(&[], false));
+    assert_eq!(
+        Ok(()),
+        Serial::validate_increasing(&[Serial::new_unchecked(7)], false)
+    );
+}
+
+#[test]
+#[cfg(feature = "arbitrary")]
+fn arbitrary_biased_to_candidates() {
+    use arbitrary::{Arbitrary, Unstructured};
+
+    // xorshift, to have reproducible input data
+    let mut state: u32 = 0x1234_5678;
+    let raw_data: std::vec::Vec<u8> = (0..30_000)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 17;
+            state ^= state << 5;
+            state.to_le_bytes()[0]
+        })
+        .collect();
+
+    let mut unstructured = Unstructured::new(&raw_data);
+    let mut total = 0;
+    let mut candidates = 0;
+    let mut seen = std::collections::BTreeSet::new();
+    while !unstructured.is_empty() {
+        let serial = Serial::arbitrary(&mut unstructured).unwrap();
+        total += 1;
+        if CANDIDATES.contains(&serial.0) {
+            candidates += 1;
+        }
+        seen.insert(serial.0);
+    }
+
+    // roughly one in four numbers is a candidate
+    assert!(total > 9_000, "{total}");
+    assert!(candidates * 5 > total, "{candidates}/{total}");
+    assert!(candidates * 3 < total, "{candidates}/{total}");
+
+    // but the full range is still covered
+    assert!(CANDIDATES.iter().all(|n| seen.contains(n)));
+    assert!(seen.len() > 5_000, "{}", seen.len());
+    assert!(seen.iter().any(|&n| n > MID_U16 + 1 && n < MAX_U16 - 2));
+
+    assert_eq!((2, Some(3)), Serial::size_hint(0));
+}
+
+#[test]
+fn staleness() {
+    let oldest_live = Serial::new_unchecked(MAX_U16 - 100);
+    let at = |dist: u16| oldest_live.wrapping_add(dist).staleness(oldest_live);
+
+    assert_eq!(Staleness::Fresh, at(0));
+    assert_eq!(Staleness::Fresh, at(200)); // across the wraparound
+    assert_eq!(Staleness::Fresh, at(16_383));
+    assert_eq!(Staleness::Approaching, at(16_384));
+    assert_eq!(Staleness::Approaching, at(29_490));
+    assert_eq!(Staleness::Critical, at(29_491));
+    assert_eq!(Staleness::Critical, at(MID_U16));
+
+    assert_eq!(Staleness::Critical, Serial::NAN.staleness(oldest_live));
+    assert_eq!(Staleness::Critical, oldest_live.staleness(Serial::NAN));
+
+    // 50% and 90% of the window
+    assert_eq!(16_384, (u32::from(MID_U16) + 1) / 2);
+    assert_eq!(29_491, (u32::from(MID_U16) * 9).div_ceil(10));
+}
+
+/// Checks the invariants of a serial number type with the given sentinel for `NAN`.
+macro_rules! check_sentinel_invariants {
+    ($ty:ident, $sentinel:expr) => {{
+        let sentinel: u16 = $sentinel;
+        let raw = [
+            0,
+            1,
+            2,
+            MID_U16 - 1,
+            MID_U16,
+            MID_U16 + 1,
+            MID_U16 + 2,
+            MAX_U16 - 1,
+            MAX_U16,
+            u16::MAX,
+            sentinel,
+            sentinel.wrapping_add(1),
+            sentinel.wrapping_sub(1),
+        ];
+        assert_eq!($ty::NAN, $ty::new_unchecked(sentinel));
+        assert_eq!($ty::ZERO, $ty::new_unchecked(sentinel.wrapping_add(1)));
+        assert!(!$ty::default().is_nan());
+
+        for n in raw {
+            let a = $ty::new_unchecked(n);
+            assert_eq!(n == sentinel, a.is_nan(), "{a:?}");
+            assert_eq!(a, $ty::from_serial(a.to_serial()));
+            assert_eq!(a.is_nan(), a.to_serial().is_nan());
+
+            // increase
+            let mut next = a;
+            next.increase();
+            if a.is_nan() {
+                assert!(next.is_nan());
+                assert!((a + 1).is_nan());
+            } else {
+                assert!(!next.is_nan(), "{a:?}");
+                assert!(next.succeeds(a), "{a:?}");
+                assert_eq!(1, next.dist(a));
+                assert_eq!(next, a + 1);
+                assert_eq!(a, a + u16::MAX);
+            }
+            assert_eq!(a, a + 0);
+
+            // or & take
+            assert_eq!(a, a.or($ty::NAN));
+            assert_eq!(a.or($ty::default()), a.or_default());
+            let mut taken = a;
+            assert_eq!(a, taken.take());
+            assert!(taken.is_nan());
+
+            // parsing
+            let text = if a.is_nan() {
+                std::format!("NaN")
+            } else {
+                std::format!("{}", a.to_serial().0)
+            };
+            assert_eq!(Ok(a), text.parse::<$ty>(), "{text}");
+
+            for m in raw {
+                let b = $ty::new_unchecked(m);
+
+                // distance and difference
+                assert_eq!(a.dist(b), b.dist(a));
+                assert!(a.dist(b) <= MID_U16);
+                assert_eq!(a.dist(b), a.diff(b).unsigned_abs());
+                if a.is_nan() ^ b.is_nan() {
+                    assert_eq!(MID_U16, a.dist(b));
+                }
+                if a == b {
+                    assert_eq!(0, a.dist(b));
+                }
+
+                // comparison
+                match a.partial_cmp(b) {
+                    None => assert!(a.is_nan() || b.is_nan()),
+                    Some(Ordering::Equal) => assert_eq!(a, b),
+                    Some(Ordering::Less) => {
+                        assert!(a.precedes(b) && a.precedes_or_eq(b));
+                        assert!(!a.succeeds(b) && !a.succeeds_or_eq(b));
+                        assert!(a.diff(b) < 0);
+                        assert_eq!((a, b), (a.min(b), a.max(b)));
+                    }
+                    Some(Ordering::Greater) => {
+                        assert!(a.succeeds(b) && a.succeeds_or_eq(b));
+                        assert!(!a.precedes(b) && !a.precedes_or_eq(b));
+                        assert!(a.diff(b) > 0);
+                        assert_eq!((b, a), (a.min(b), a.max(b)));
+                    }
+                }
+                assert_eq!(a.partial_cmp(b).map(Ordering::reverse), b.partial_cmp(a));
+                if !a.is_nan() && !b.is_nan() {
+                    assert_eq!(a.partial_cmp(b), Some(a.cmp_unchecked(b)));
+                }
+
+                // the sentinel does not change the arithmetic
+                let (x, y) = (a.to_serial(), b.to_serial());
+                assert_eq!(x.dist(y), a.dist(b));
+                assert_eq!(x.xor_metric(y), a.xor_metric(b));
+                assert_eq!(x.diff(y), a.diff(b));
+                assert_eq!(x.partial_cmp(y), a.partial_cmp(b));
+                assert_eq!(x.min(y), a.min(b).to_serial());
+                assert_eq!(x.max(y), a.max(b).to_serial());
+                assert_eq!(x + m, (a + m).to_serial());
+            }
+        }
+    }};
+}
+
+#[test]
+fn sentinel_invariants() {
+    type SevenNanSerial = SentinelSerial<7>;
+
+    check_sentinel_invariants!(Serial, u16::MAX);
+    check_sentinel_invariants!(ZeroNanSerial, 0);
+    check_sentinel_invariants!(SevenNanSerial, 7);
+}
+
+#[test]
+fn sentinel_conversions() {
+    type SevenNanSerial = SentinelSerial<7>;
+
+    assert_eq!(Serial::NAN, SevenNanSerial::NAN.to_serial());
+    assert_eq!(Serial::ZERO, SevenNanSerial::ZERO.to_serial());
+    assert_eq!(Serial::HALF, SevenNanSerial::HALF.to_serial());
+    assert_eq!(SevenNanSerial::new_unchecked(8), SevenNanSerial::ZERO);
+    assert_eq!(
+        SevenNanSerial::new_unchecked(6),
+        SevenNanSerial::ZERO + MAX_U16
+    );
+    assert_eq!(Serial::NAN, Serial::from_serial(Serial::NAN));
+    assert_eq!(
+        Serial::new_unchecked(42),
+        Serial::new_unchecked(42).to_serial()
+    );
+
+    assert_eq!(
+        "SentinelSerial(NaN)",
+        std::format!("{:?}", SevenNanSerial::NAN)
+    );
+    assert_eq!(
+        "SentinelSerial(8)",
+        std::format!("{:?}", SevenNanSerial::ZERO)
+    );
+    assert_eq!("Serial(NaN)", std::format!("{:?}", Serial::NAN));
+}
+
+#[test]
+#[cfg(feature = "serde_json")]
+fn to_json_value() {
+    use serde_json::Value;
+
+    assert_eq!(Value::from(0), Serial::new_unchecked(0).to_json_value());
+    assert_eq!(
+        Value::from(MAX_U16),
+        Serial::new_unchecked(MAX_U16).to_json_value()
+    );
+    assert_eq!(Value::Null, Serial::NAN.to_json_value());
+    assert!(Serial::new_unchecked(42).to_json_value().is_u64());
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        let value = expected.to_json_value();
+        let actual: Option<Serial> = serde_json::from_value(value).unwrap();
+        assert_eq!(expected, actual.unwrap_or(Serial::NAN));
+    }
+
+    // the number is the one that serde stores
+    assert_eq!(Value::Null, ZeroNanSerial::NAN.to_json_value());
+    assert_eq!(Value::from(1), ZeroNanSerial::ZERO.to_json_value());
+    assert_eq!(
+        serde_json::to_value(ZeroNanSerial::ZERO).unwrap(),
+        ZeroNanSerial::ZERO.to_json_value()
+    );
+}
+
+#[test]
+fn time_since() {
+    use core::time::Duration;
+
+    let tick = Duration::from_micros(1500);
+    assert_eq!(
+        Some(Duration::ZERO),
+        Serial::new_unchecked(7).time_since(Serial::new_unchecked(7), tick)
+    );
+    assert_eq!(
+        Some(Duration::from_micros(4500)),
+        Serial::new_unchecked(1).time_since(Serial::new_unchecked(MAX_U16 - 1), tick)
+    );
+    assert_eq!(
+        Some(Duration::from_micros(4500)),
+        Serial::new_unchecked(MAX_U16 - 1).time_since(Serial::new_unchecked(1), tick)
+    );
+    assert_eq!(
+        Some(tick * u32::from(MID_U16)),
+        Serial::new_unchecked(MID_U16).time_since(Serial::new_unchecked(0), tick)
+    );
+
+    assert_eq!(None, Serial::NAN.time_since(Serial::new_unchecked(0), tick));
+    assert_eq!(None, Serial::new_unchecked(0).time_since(Serial::NAN, tick));
+    assert_eq!(None, Serial::NAN.time_since(Serial::NAN, tick));
+
+    assert_eq!(
+        None,
+        Serial::new_unchecked(2).time_since(Serial::new_unchecked(0), Duration::MAX)
+    );
+    assert_eq!(
+        Some(Duration::MAX),
+        Serial::new_unchecked(1).time_since(Serial::new_unchecked(0), Duration::MAX)
+    );
+}
+
+#[test]
+#[cfg(feature = "bincode")]
+fn bincode_fixed() {
+    let cfg = bincode::config::standard().with_fixed_int_encoding();
+
+    for n in CANDIDATES {
+        let expected = Serial::new_unchecked(n);
+        let encoded = expected.bincode_encode_fixed();
+
+        let mut buf = [0_u8; 2];
+        assert_eq!(
+            2,
+            bincode::encode_into_slice(expected, &mut buf, cfg).unwrap()
+        );
+        assert_eq!(buf, encoded);
+
+        assert_eq!(expected, Serial::bincode_decode_fixed(&encoded));
+        let (actual, _): (Serial, _) = bincode::decode_from_slice(&encoded, cfg).unwrap();
+        assert_eq!(expected, actual);
+    }
+
+    // like the derive, this encodes the raw value
+    assert_eq!([0, 0], ZeroNanSerial::NAN.bincode_encode_fixed());
+    assert!(ZeroNanSerial::bincode_decode_fixed(&[0, 0]).is_nan());
+
+    // the default config uses a variable-length encoding
+    let varint = bincode::config::standard();
+    let mut buf = [0_u8; 3];
+    assert_eq!(
+        1,
+        bincode::encode_into_slice(Serial::new_unchecked(0), &mut buf, varint).unwrap()
+    );
+    assert_eq!(
+        3,
+        bincode::encode_into_slice(Serial::new_unchecked(MAX_U16), &mut buf, varint).unwrap()
+    );
+}
+
+#[test]
+fn contiguous_run() {
+    let start = Serial::new_unchecked(10);
+    assert_eq!(
+        3,
+        Serial::contiguous_run(
+            start,
+            &[
+                Serial::new_unchecked(10),
+                Serial::new_unchecked(11),
+                Serial::new_unchecked(12)
+            ]
+        )
+    );
+    assert_eq!(
+        1,
+        Serial::contiguous_run(
+            start,
+            &[
+                Serial::new_unchecked(10),
+                Serial::new_unchecked(12),
+                Serial::new_unchecked(13)
+            ]
+        )
+    );
+    assert_eq!(
+        2,
+        Serial::contiguous_run(
+            start,
+            &[
+                Serial::new_unchecked(10),
+                Serial::new_unchecked(11),
+                Serial::new_unchecked(11)
+            ]
+        )
+    );
+    assert_eq!(
+        0,
+        Serial::contiguous_run(
+            start,
+            &[Serial::new_unchecked(11), Serial::new_unchecked(12)]
+        )
+    );
+    assert_eq!(0, Serial::contiguous_run(start, &[]));
+
+    // across the wraparound
+    let start = Serial::new_unchecked(MAX_U16 - 1);
+    let received = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(3),
+    ];
+    assert_eq!(4, Serial::contiguous_run(start, &received));
+
+    // NAN ends the run
+    let received = [
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::NAN,
+        Serial::new_unchecked(0),
+    ];
+    assert_eq!(1, Serial::contiguous_run(start, &received));
+    assert_eq!(
+        0,
+        Serial::contiguous_run(Serial::NAN, &[Serial::NAN, Serial::NAN])
+    );
+
+    // a run over the whole ring is capped
+    let received: std::vec::Vec<Serial> = core::iter::once(Serial::new_unchecked(0))
+        .chain(Serial::new_unchecked(0).successors())
+        .take(70_000)
+        .collect();
+    assert_eq!(
+        u16::MAX,
+        Serial::contiguous_run(Serial::new_unchecked(0), &received)
+    );
+}
+
+#[test]
+fn cmp_unchecked() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
+            if a.is_nan() || b.is_nan() {
+                continue;
+            }
+            assert_eq!(a.partial_cmp(b), Some(a.cmp_unchecked(b)), "{a:?} {b:?}");
+        }
+    }
+
+    // NAN gives a meaningless result, but does not panic
+    let _ = Serial::NAN.cmp_unchecked(Serial::new_unchecked(0));
+    let _ = Serial::new_unchecked(0).cmp_unchecked(Serial::NAN);
+    let _ = Serial::NAN.cmp_unchecked(Serial::NAN);
+}
+
+#[test]
+#[cfg(feature = "alloc")]
+fn encode_deltas() {
+    // a realistic stream with reordering and a wrap
+    let serials = [
+        Serial::new_unchecked(MAX_U16 - 2),
+        Serial::new_unchecked(MAX_U16 - 1),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MAX_U16),
+        Serial::new_unchecked(1),
+        Serial::new_unchecked(5),
+    ];
+    let (base, deltas) = Serial::encode_deltas(&serials);
+    assert_eq!(Serial::new_unchecked(MAX_U16 - 2), base);
+    assert_eq!(std::vec![1, 2, -1, 2, 4], deltas);
+    assert_eq!(serials.to_vec(), Serial::decode_deltas(base, &deltas));
+
+    // the largest differences in both directions
+    let serials = [
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MID_U16),
+        Serial::new_unchecked(0),
+        Serial::new_unchecked(MID_U16 + 1),
+    ];
+    let (base, deltas) = Serial::encode_deltas(&serials);
+    assert_eq!(std::vec![32767, -32767, -32767], deltas);
+    assert_eq!(serials.to_vec(), Serial::decode_deltas(base, &deltas));
+
+    // NAN does not break the chain
+    let serials = [
+        Serial::NAN,
+        Serial::NAN,
+        Serial::new_unchecked(3),
+        Serial::NAN,
+        Serial::new_unchecked(4),
+    ];
+    let (base, deltas) = Serial::encode_deltas(&serials);
+    assert_eq!(Serial::NAN, base);
+    assert_eq!(std::vec![i16::MIN, 3, i16::MIN, 1], deltas);
+    assert_eq!(serials.to_vec(), Serial::decode_deltas(base, &deltas));
+
+    let (base, deltas) = Serial::encode_deltas(&[]);
+    assert_eq!(Serial::NAN, base);
+    assert!(deltas.is_empty());
+    assert_eq!(std::vec![Serial::NAN], Serial::decode_deltas(base, &deltas));
+}
+
+#[test]
+fn xor_metric() {
+    let a = Serial::new_unchecked(0);
+    assert_eq!(Some(0), a.xor_metric(a));
+    assert_eq!(Some(1), a.xor_metric(Serial::new_unchecked(1)));
+
+    // neighbours on the ring, but far apart in bits
+    assert_eq!(
+        Some(16),
+        Serial::new_unchecked(MID_U16).xor_metric(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(
+        1,
+        Serial::new_unchecked(MID_U16).dist(Serial::new_unchecked(MID_U16 + 1))
+    );
+    assert_eq!(
+        Some(15),
+        Serial::new_unchecked(MAX_U16).xor_metric(Serial::new_unchecked(0))
+    );
+    assert_eq!(
+        1,
+        Serial::new_unchecked(MAX_U16).dist(Serial::new_unchecked(0))
+    );
+
+    // far apart on the ring, but close in bits
+    assert_eq!(Some(1), a.xor_metric(Serial::new_unchecked(0x4000)));
+    assert_eq!(0x4000, a.dist(Serial::new_unchecked(0x4000)));
+
+    assert_eq!(None, a.xor_metric(Serial::NAN));
+    assert_eq!(None, Serial::NAN.xor_metric(a));
+    assert_eq!(None, Serial::NAN.xor_metric(Serial::NAN));
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn zero_nan_serde_transparent() {
+    let named_config = || ron::ser::PrettyConfig::new().struct_names(true);
+
+    // the same wire format as Serial
+    for n in CANDIDATES {
+        let zero_nan = ZeroNanSerial::new_unchecked(n);
+        assert_eq!(
+            ron::ser::to_string_pretty(&zero_nan.0, named_config()).unwrap(),
+            ron::ser::to_string_pretty(&zero_nan, named_config()).unwrap()
+        );
+        assert_eq!(
+            zero_nan,
+            ron::from_str::<ZeroNanSerial>(&ron::to_string(&zero_nan).unwrap()).unwrap()
+        );
+    }
+    assert_eq!(
+        "42",
+        ron::to_string(&ZeroNanSerial::new_unchecked(42)).unwrap()
+    );
+}
+
+#[test]
+#[cfg(not(feature = "unsafe-total-order"))]
+fn cmp_alias() {
+    for n in CANDIDATES {
+        for m in CANDIDATES {
+            let a = Serial::new_unchecked(n);
+            let b = Serial::new_unchecked(m);
+            assert_eq!(a.partial_cmp(b), a.cmp(b));
+        }
+    }
+}
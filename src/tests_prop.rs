@@ -74,13 +74,22 @@ proptest! {
         }
     }
 
+    #[test]
+    fn diff_agrees_with_cmp(a in arb::<Serial>(), b in arb::<Serial>()) {
+        if a.is_nan() || b.is_nan() {
+            return Ok(());
+        }
+        assert_eq!(a.partial_cmp(b), Some(a.diff(b).cmp(&0)));
+        assert_eq!(a.dist(b), a.diff(b).unsigned_abs());
+    }
+
     #[test]
     fn or(num in arb::<Serial>()) {
         assert_eq!(num.or(Serial::NAN), num);
         if num.is_nan() {
-            assert_eq!(num.or(Serial(5)), Serial(5));
+            assert_eq!(num.or(Serial::new_unchecked(5)), Serial::new_unchecked(5));
         } else {
-            assert_eq!(num.or(Serial(5)), num);
+            assert_eq!(num.or(Serial::new_unchecked(5)), num);
         }
     }
 
@@ -185,4 +194,17 @@ proptest! {
         let actual: Serial = bitcode::decode(&encoded).unwrap();
         assert_eq!(expected, actual);
     }
+
+    #[test]
+    #[cfg(feature = "databuf")]
+    fn databuf_roundtrip(expected in arb::<Serial>()) {
+        use databuf::config::num::LE;
+        use databuf::{Decode, Encode};
+
+        let encoded = expected.to_bytes::<LE>();
+        assert_eq!(2, encoded.len());
+
+        let actual = Serial::from_bytes::<LE>(&encoded).unwrap();
+        assert_eq!(expected, actual);
+    }
 }